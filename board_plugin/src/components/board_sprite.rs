@@ -0,0 +1,16 @@
+use bevy::prelude::Component;
+
+/// Board sprite component, indicates which `BoardAssets` material a sprite uses
+/// so it can be re-skinned when the theme changes
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub enum BoardSprite {
+    Board,
+    Tile,
+    CoveredTile,
+    Flag,
+    Bomb,
+    Hint,
+    WrongFlag,
+    ExplodedTile,
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 use bevy::prelude::Component;
 
-/// Uncover component, indicates a covered tile that should be uncovered
+/// Flagged component, indicates a covered tile marked by the player
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
-pub struct Uncover;
\ No newline at end of file
+pub struct Flagged;
\ No newline at end of file
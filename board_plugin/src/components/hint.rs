@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Hint component, marks the overlay highlighting a hinted tile
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Hint;
\ No newline at end of file
@@ -1,10 +1,17 @@
-pub use coordinates::Coordinates;
+pub use minesweeper_core::Coordinates;
 
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
-pub use uncover::Uncover;
+pub use board_sprite::BoardSprite;
+pub use flagged::Flagged;
+pub use hint::Hint;
+pub use pause_overlay::PauseOverlay;
+pub use tile_coordinates::TileCoordinates;
 
-mod coordinates;
 mod bomb;
 mod bomb_neighbor;
-mod uncover;
\ No newline at end of file
+mod board_sprite;
+mod flagged;
+mod hint;
+mod pause_overlay;
+mod tile_coordinates;
\ No newline at end of file
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Pause overlay component, marks the sprite hiding the board while the game is paused
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct PauseOverlay;
\ No newline at end of file
@@ -0,0 +1,34 @@
+use std::ops::Deref;
+use bevy::prelude::Component;
+use minesweeper_core::Coordinates;
+
+/// Position of a tile entity on the board
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct TileCoordinates(pub Coordinates);
+
+impl Deref for TileCoordinates {
+    type Target = Coordinates;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "debug")]
+impl bevy_inspector_egui::Inspectable for TileCoordinates {
+    type Attributes = ();
+
+    fn ui(
+        &mut self,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        _: Self::Attributes,
+        context: &mut bevy_inspector_egui::Context,
+    ) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            changed |= self.0.x.ui(ui, Default::default(), &mut context.with_id(0));
+            changed |= self.0.y.ui(ui, Default::default(), &mut context.with_id(1));
+        });
+        changed
+    }
+}
\ No newline at end of file
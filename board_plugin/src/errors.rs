@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::io;
+
+/// Errors raised while saving or loading a game or a replay
+#[derive(Debug)]
+pub enum VersionedFileError {
+    Io(io::Error),
+    Parse(ron::Error),
+    /// The file was written by an incompatible version of the game
+    UnsupportedVersion { version: u32, expected: u32 },
+    /// The content doesn't match itself, like a board not matching its options
+    Corrupted,
+}
+
+impl Display for VersionedFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not access the file: {}", e),
+            Self::Parse(e) => write!(f, "malformed file: {}", e),
+            Self::UnsupportedVersion { version, expected } => write!(
+                f,
+                "file version {} is not supported, expected {}",
+                version, expected
+            ),
+            Self::Corrupted => write!(f, "corrupted file"),
+        }
+    }
+}
+
+impl Error for VersionedFileError {}
\ No newline at end of file
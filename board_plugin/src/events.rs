@@ -1,4 +1,44 @@
-use crate::components::Coordinates;
+use std::path::PathBuf;
+use minesweeper_core::{Change, Coordinates};
 
 #[derive(Debug, Clone, Copy)]
-pub struct TileTriggerEvent(pub Coordinates);
\ No newline at end of file
+pub struct TileTriggerEvent(pub Coordinates);
+
+#[derive(Debug, Clone, Copy)]
+pub struct TileMarkEvent(pub Coordinates);
+
+/// Uncovers every unflagged neighbor of a revealed tile once its bomb count is flagged
+#[derive(Debug, Clone, Copy)]
+pub struct TileChordEvent(pub Coordinates);
+
+/// Sent for every change of the game caused by the tile events, for the board to render it
+#[derive(Debug, Clone, Copy)]
+pub struct GameChangeEvent(pub Change);
+
+/// Sent when a bomb is uncovered, carrying the coordinates of the detonated tile
+#[derive(Debug, Clone, Copy)]
+pub struct BombExplosionEvent(pub Coordinates);
+
+/// Sent when every safe tile of the board has been uncovered
+#[derive(Debug, Clone, Copy)]
+pub struct BoardCompletedEvent;
+
+/// Asks for the safest covered tile to be highlighted
+#[derive(Debug, Clone, Copy)]
+pub struct HintRequestEvent;
+
+/// Writes the game in progress to a file
+#[derive(Debug, Clone)]
+pub struct SaveGameEvent(pub PathBuf);
+
+/// Replaces the game in progress with the one saved in a file
+#[derive(Debug, Clone)]
+pub struct LoadGameEvent(pub PathBuf);
+
+/// Writes the inputs of the game in progress and its mine layout to a replay file
+#[derive(Debug, Clone)]
+pub struct SaveReplayEvent(pub PathBuf);
+
+/// Replaces the game in progress with the playback of a replay file
+#[derive(Debug, Clone)]
+pub struct PlayReplayEvent(pub PathBuf);
\ No newline at end of file
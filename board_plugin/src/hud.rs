@@ -0,0 +1,203 @@
+use bevy::ecs::schedule::StateData;
+use bevy::{log, prelude::*, ui::FocusPolicy};
+use crate::events::{BoardCompletedEvent, BombExplosionEvent};
+use crate::resources::{Board, BoardAssets, GameStats};
+
+/// Root node of the HUD
+#[derive(Debug, Copy, Clone, Component)]
+pub struct Hud;
+
+/// Text showing the bombs left to flag
+#[derive(Debug, Copy, Clone, Component)]
+pub struct BombCounterText;
+
+/// Text showing the elapsed game time
+#[derive(Debug, Copy, Clone, Component)]
+pub struct TimerText;
+
+/// Button restarting the game
+#[derive(Debug, Copy, Clone, Component)]
+pub struct ResetButton;
+
+/// Face of the reset button, reflecting the game outcome
+#[derive(Debug, Copy, Clone, Component)]
+pub struct ResetFace;
+
+/// Height of the bar on top of the window, the board is laid out below it
+pub const HUD_HEIGHT: f32 = 50.0;
+const FONT_SIZE: f32 = 30.0;
+const FACE_PLAYING: &str = ":)";
+const FACE_WON: &str = "B)";
+const FACE_LOST: &str = "X(";
+
+/// Displays the bomb counter, the game timer and a reset button above the board
+pub struct HudPlugin<T> {
+    pub running_state: T,
+}
+
+impl<T: StateData> Plugin for HudPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(self.running_state.clone())
+                .with_system(Self::spawn_hud)
+        )
+        .add_system_set(
+            SystemSet::on_update(self.running_state.clone())
+                .with_system(Self::update_bomb_counter)
+                .with_system(Self::update_timer)
+                .with_system(Self::reset_button_handler)
+        )
+        // The game may be paused as soon as it is over
+        .add_system_set(
+            SystemSet::on_in_stack_update(self.running_state.clone())
+                .with_system(Self::update_reset_face)
+        )
+        .add_system_set(
+            SystemSet::on_exit(self.running_state.clone())
+                .with_system(Self::despawn_hud)
+        );
+        log::info!("Loaded HUD Plugin");
+    }
+}
+
+impl<T: StateData> HudPlugin<T> {
+    fn reset_button_handler(
+        mut state: ResMut<State<T>>,
+        buttons: Query<&Interaction, (Changed<Interaction>, With<ResetButton>)>,
+    ) {
+        for interaction in buttons.iter() {
+            if *interaction == Interaction::Clicked {
+                log::info!("resetting game");
+                if let Err(e) = state.restart() {
+                    log::error!("Failed to reset the game: {}", e);
+                }
+            }
+        }
+    }
+}
+
+impl<T> HudPlugin<T> {
+    fn text_bundle(value: &str, board_assets: &BoardAssets, color: Color) -> TextBundle {
+        TextBundle {
+            text: Text::with_section(
+                value,
+                TextStyle {
+                    font: board_assets.bomb_counter_font.clone(),
+                    font_size: FONT_SIZE,
+                    color,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    pub fn spawn_hud(mut commands: Commands, board_assets: Res<BoardAssets>) {
+        // Bevy UI is laid out from the bottom, reversing the column puts the bar on top
+        commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::ColumnReverse,
+                    ..Default::default()
+                },
+                color: Color::NONE.into(),
+                // Covers the whole window, clicks must reach the menus below
+                focus_policy: FocusPolicy::Pass,
+                ..Default::default()
+            })
+            .insert(Name::new("HUD"))
+            .insert(Hud)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Percent(100.0), Val::Px(HUD_HEIGHT)),
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            padding: Rect::all(Val::Px(10.0)),
+                            ..Default::default()
+                        },
+                        color: Color::NONE.into(),
+                        focus_policy: FocusPolicy::Pass,
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent
+                            .spawn_bundle(Self::text_bundle("000", &board_assets, Color::RED))
+                            .insert(Name::new("Bomb Counter"))
+                            .insert(BombCounterText);
+                        parent
+                            .spawn_bundle(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(HUD_HEIGHT), Val::Px(HUD_HEIGHT - 10.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                color: Color::GRAY.into(),
+                                ..Default::default()
+                            })
+                            .insert(Name::new("Reset Button"))
+                            .insert(ResetButton)
+                            .with_children(|parent| {
+                                parent
+                                    .spawn_bundle(Self::text_bundle(FACE_PLAYING, &board_assets, Color::YELLOW))
+                                    .insert(ResetFace);
+                            });
+                        parent
+                            .spawn_bundle(Self::text_bundle("000", &board_assets, Color::RED))
+                            .insert(Name::new("Timer"))
+                            .insert(TimerText);
+                    });
+            });
+    }
+
+    fn set_text(text: &mut Text, value: String) {
+        // Only touch the text when needed, changing it triggers a new layout
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    fn update_bomb_counter(
+        board: Res<Board>,
+        mut texts: Query<&mut Text, With<BombCounterText>>,
+    ) {
+        for mut text in texts.iter_mut() {
+            Self::set_text(&mut text, format!("{:03}", board.game.remaining_bombs()));
+        }
+    }
+
+    fn update_timer(
+        game_stats: Res<GameStats>,
+        mut texts: Query<&mut Text, With<TimerText>>,
+    ) {
+        for mut text in texts.iter_mut() {
+            Self::set_text(&mut text, format!("{:03}", game_stats.elapsed.as_secs()));
+        }
+    }
+
+    fn update_reset_face(
+        mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+        mut board_completed_evr: EventReader<BoardCompletedEvent>,
+        mut texts: Query<&mut Text, With<ResetFace>>,
+    ) {
+        let face = if bomb_explosion_evr.iter().count() > 0 {
+            FACE_LOST
+        } else if board_completed_evr.iter().count() > 0 {
+            FACE_WON
+        } else {
+            return;
+        };
+        for mut text in texts.iter_mut() {
+            Self::set_text(&mut text, face.to_string());
+        }
+    }
+
+    fn despawn_hud(mut commands: Commands, huds: Query<Entity, With<Hud>>) {
+        for entity in huds.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
\ No newline at end of file
@@ -1,16 +1,24 @@
 pub mod components;
 pub mod resources;
 pub mod systems;
+pub mod errors;
+pub mod events;
+pub mod hud;
 mod bounds;
-mod events;
+
+pub use minesweeper_core::solver;
 
 use bevy::ecs::schedule::StateData;
+use bevy::ecs::system::EntityCommands;
+use minesweeper_core::{Change, Game, GameOptions, Tile};
+use rand::{thread_rng, Rng};
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::InspectableRegistry;
 
 use bevy::{log, prelude::*, math::Vec3Swizzles, utils::HashMap};
-use resources::{tile_map::TileMap, BoardOptions, tile::Tile, TileSize, BoardPosition, Board, BoardAssets};
-use components::{Coordinates, BombNeighbor, Bomb, Uncover};
+use hud::HUD_HEIGHT;
+use resources::{BoardOptions, TileSize, BoardPosition, Board, BoardAssets, BoardAssetsLoader, GameStats, ReplayPlayer, ReplayRecorder, SavedGame};
+use components::{Coordinates, BombNeighbor, Bomb, BoardSprite, Flagged, Hint, PauseOverlay, TileCoordinates};
 use crate::events::*;
 
 use crate::bounds::Bounds2;
@@ -31,26 +39,65 @@ impl <T: StateData>Plugin for BoardPlugin<T> {
         app.add_system_set(
             SystemSet::on_enter(self.running_state.clone())
                 .with_system(Self::create_board)
+                .with_system(systems::input::reset_board_input)
         )
+        // The board is hidden while another state is pushed on top of it
         .add_system_set(
             SystemSet::on_pause(self.running_state.clone())
+                .with_system(systems::pause::pause_board)
+        )
+        .add_system_set(
+            SystemSet::on_resume(self.running_state.clone())
+                .with_system(systems::pause::resume_board)
         )
         // We handle input and trigger events only if the state is active
         .add_system_set(
             SystemSet::on_update(self.running_state.clone())
                 .with_system(systems::input::input_handling)
-                .with_system(systems::uncover::trigger_event_handler),
+                .with_system(systems::input::hint_input_handling)
+                .with_system(systems::hint::hint_request_handler)
+                .with_system(systems::timer::tick_game_timer)
+                .with_system(systems::uncover::trigger_event_handler)
+                .with_system(systems::uncover::chord_event_handler)
+                .with_system(systems::mark::mark_event_handler)
+                .with_system(systems::save::save_game_handler)
+                .with_system(systems::save::load_game_handler::<T>)
+                .with_system(systems::replay::record_inputs)
+                .with_system(systems::replay::play_replay_handler::<T>)
+                .with_system(systems::replay::play_replay::<T>),
         )
         // We handle uncovering even if the state is inactive
         .add_system_set(
             SystemSet::on_in_stack_update(self.running_state.clone())
-                .with_system(systems::uncover::uncover_tiles),   
+                .with_system(systems::uncover::uncover_tiles)
+                .with_system(systems::mark::mark_tiles)
+                .with_system(systems::game_over::reveal_board)
+                .with_system(Self::spawn_deferred_board)
+                .with_system(systems::theme::reskin_board)
+                // Finished games can be saved as replays
+                .with_system(systems::replay::save_replay_handler),
         )
         .add_system_set(
             SystemSet::on_exit(self.running_state.clone())
                 .with_system(Self::cleanup_board)
         )
-        .add_event::<TileTriggerEvent>();
+        .init_resource::<systems::input::BoardInput>()
+        .add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .add_event::<GameChangeEvent>()
+        .add_event::<BombExplosionEvent>()
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<HintRequestEvent>()
+        .add_event::<SaveGameEvent>()
+        .add_event::<LoadGameEvent>()
+        .add_event::<SaveReplayEvent>()
+        .add_event::<PlayReplayEvent>()
+        // Themes can be loaded, applied and reloaded in any state
+        .add_asset::<BoardAssets>()
+        .init_asset_loader::<BoardAssetsLoader>()
+        .add_system(systems::theme::apply_board_theme)
+        .add_system(systems::theme::reload_fonts);
 
         #[cfg(feature = "debug")]
         {
@@ -58,9 +105,13 @@ impl <T: StateData>Plugin for BoardPlugin<T> {
                 .world
                 .get_resource_or_insert_with(InspectableRegistry::default);
 
-            registry.register::<Coordinates>();
+            registry.register::<TileCoordinates>();
             registry.register::<BombNeighbor>();
             registry.register::<Bomb>();
+            registry.register::<Flagged>();
+            registry.register::<Hint>();
+            registry.register::<PauseOverlay>();
+            registry.register::<BoardSprite>();
             // app.register_inspectable::<Uncover>();
         }
         log::info!("Loaded Board Plugin");
@@ -68,17 +119,31 @@ impl <T: StateData>Plugin for BoardPlugin<T> {
 }
 
 impl<T> BoardPlugin<T> {
+    /// Spawns a new board, or the pending `SavedGame` if there is one
     pub fn create_board(
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
+        saved_game: Option<Res<SavedGame>>,
+        replay_player: Option<ResMut<ReplayPlayer>>,
         board_assets: Res<BoardAssets>,
         window: Res<WindowDescriptor>,
     ) {
-        let options = match board_options {
-            None => BoardOptions::default(),
-            Some(o) => o.clone(),
+        let saved_game = match saved_game {
+            Some(saved_game) => {
+                commands.remove_resource::<SavedGame>();
+                saved_game.clone()
+            }
+            None => {
+                let options = match board_options {
+                    None => BoardOptions::default(),
+                    Some(o) => o.clone(),
+                };
+                Self::new_game(options)
+            }
         };
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
+        let mut game = saved_game.game();
+        let options = saved_game.options.clone();
+        let tile_map = game.tile_map();
         let tile_size = match options.tile_size {
             TileSize::Fixed(v) => v,
             TileSize::Adaptative { min, max } => Self::adaptative_tile_size(
@@ -92,20 +157,29 @@ impl<T> BoardPlugin<T> {
             tile_map.height() as f32 * tile_size,
         );
         let board_position = match options.position {
+            // Centered in the space left below the HUD
             BoardPosition::Centered { offset } => { Vec3::new(
                 -(board_size.x / 2.0) ,
-                -(board_size.y / 2.0),
+                -(board_size.y / 2.0) - HUD_HEIGHT / 2.0,
                 0.0) + offset
             }
             BoardPosition::Custom(p) => p,
         };
         
-        let mut covered_tiles = HashMap::with_capacity((tile_map.width() * tile_map.height()).into());
-        let mut safe_start = None;
+        #[cfg(feature = "debug")]
+        if game.generated() {
+            log::info!("{}", tile_map.console_output());
+        }
+
+        if options.game.safe_start {
+            game.safe_start();
+        }
+
+        let mut covered_tiles = HashMap::default();
 
         log::info!("Board size: {}", board_size);
-        tile_map.set_bombs(40);
-        
+        log::info!("Board seed: {}", game.seed());
+
         let board_entity = commands.spawn()
             .insert(Name::new("Board"))
             .insert(Transform::from_translation(board_position))
@@ -122,39 +196,84 @@ impl<T> BoardPlugin<T> {
                         transform: Transform::from_xyz(board_size.x / 2.0, board_size.y / 2.0, 0.0),
                         ..Default::default()
                     })
-                    .insert(Name::new("Background"));
+                    .insert(Name::new("Background"))
+                    .insert(BoardSprite::Board);
                 Self::spawn_tiles(
                     parent,
-                    &tile_map,
-                    tile_size,
+                    &game,
                     options.tile_padding,
+                    tile_size,
                     &board_assets,
                     &mut covered_tiles,
-                    &mut safe_start,
                 )
             })
             .id();
-        #[cfg(feature = "debug")]
-        log::info!("{}", tile_map.console_output());
 
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
+        commands.insert_resource(saved_game.game_stats());
+        commands.insert_resource(saved_game.replay_recorder());
+        if let Some(mut replay_player) = replay_player {
+            replay_player.restarting = false;
         }
-
         commands.insert_resource(Board {
-            tile_map,
+            game,
+            options,
             bounds: Bounds2 {
                 position: board_position.xy(),
                 size: board_size,
             },
             tile_size,
             covered_tiles,
-            entity: board_entity
+            entity: board_entity,
         });
     }
 
+    /// A fully covered game, with its bombs placed unless generation is deferred
+    fn new_game(mut options: BoardOptions) -> SavedGame {
+        let game_options = &mut options.game;
+        let seed = game_options.seed.unwrap_or_else(|| thread_rng().gen());
+        let game = match Game::new(game_options.map_size, game_options.bomb_count, game_options.generation.clone(), seed) {
+            Ok(game) => game,
+            Err(e) => {
+                log::error!("{}, using the default game options", e);
+                options.game = GameOptions::default();
+                return Self::new_game(options);
+            }
+        };
+        // Fixed boards bring their own size and bomb count
+        let tile_map = game.tile_map();
+        game_options.map_size = (tile_map.width(), tile_map.height());
+        game_options.bomb_count = game.bomb_count();
+        SavedGame::new(options, seed, game.generated(), tile_map.clone())
+    }
+
+    /// Spawns the bomb and counter sprites of every tile once the bombs
+    /// of a deferred board are placed
+    fn spawn_deferred_board(
+        mut commands: Commands,
+        board: Res<Board>,
+        board_assets: Res<BoardAssets>,
+        mut game_change_evr: EventReader<GameChangeEvent>,
+        tiles: Query<(Entity, &TileCoordinates)>,
+    ) {
+        if !game_change_evr.iter().any(|e| e.0 == Change::Generated) {
+            return;
+        }
+        let tile_map = board.game.tile_map();
+        #[cfg(feature = "debug")]
+        log::info!("{}", tile_map.console_output());
+
+        for (entity, coords) in tiles.iter() {
+            let tile = tile_map[coords.y as usize][coords.x as usize];
+            Self::spawn_tile_content(
+                &mut commands.entity(entity),
+                &tile,
+                board.tile_size,
+                board.options.tile_padding,
+                &board_assets,
+            );
+        }
+    }
+
     fn bomb_count_text_bundle(count: u8, board_assets: &BoardAssets, size: f32) -> Text2dBundle {
         let color = board_assets.bomb_counter_color(count);
 
@@ -180,14 +299,13 @@ impl<T> BoardPlugin<T> {
 
     fn spawn_tiles(
         parent: &mut ChildBuilder,
-        tile_map: &TileMap,
-        size: f32,
+        game: &Game,
         padding: f32,
+        size: f32,
         board_assets: &BoardAssets,
         covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
     ) {
-        for (y, line) in tile_map.iter().enumerate() {
+        for (y, line) in game.tile_map().iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
                 let mut cmd = parent.spawn();
                 let coordinates = Coordinates {
@@ -198,7 +316,7 @@ impl<T> BoardPlugin<T> {
                 cmd.insert_bundle(SpriteBundle {
                     sprite: Sprite {
                         color: board_assets.tile_material.color,
-                        custom_size: Some(Vec2::splat(size - padding as f32)),
+                        custom_size: Some(Vec2::splat(size - padding)),
                         ..Default::default()
                     },
                     transform: Transform::from_xyz(
@@ -210,11 +328,12 @@ impl<T> BoardPlugin<T> {
                     ..Default::default()
                 })
                 .insert(Name::new(format!("Tile ({}, {})", x, y)))
-                .insert(coordinates);
+                .insert(BoardSprite::Tile)
+                .insert(TileCoordinates(coordinates));
 
-                cmd.with_children(|parent| {
-                    let entity = parent
-                        .spawn_bundle(SpriteBundle {
+                if game.is_covered(coordinates) {
+                    cmd.with_children(|parent| {
+                        let mut cover = parent.spawn_bundle(SpriteBundle {
                             sprite: Sprite {
                                 custom_size: Some(Vec2::splat(size - padding)),
                                 color: board_assets.covered_tile_material.color,
@@ -223,51 +342,79 @@ impl<T> BoardPlugin<T> {
                             texture: board_assets.covered_tile_material.texture.clone(),
                             transform: Transform::from_xyz(0.0, 0., 2.0),
                             ..Default::default()
-                        })
-                        .insert(Name::new("Tile Cover"))
-                        .id();
-                    covered_tiles.insert(coordinates, entity);
-                    if safe_start_entity.is_none() && *tile == Tile::Empty {
-                        *safe_start_entity = Some(entity);
-                    }
-                });
-                
-                match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb);
-                        cmd.with_children(|parent| {
-                            parent.spawn_bundle(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(size - padding)),
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0.0, 0.0, 1.0),
-                                texture: board_assets.bomb_material.texture.clone(),
-                                ..Default::default()
-                            });
-                        });
-                    }
-                    Tile::BombNeighbor(v) => {
-                        cmd.insert(BombNeighbor{ count: *v });
-                        cmd.with_children(|parent| {
-                            parent.spawn_bundle(Self::bomb_count_text_bundle(*v, board_assets, size - padding));
                         });
-                    }
-                    Tile::Empty => ()
+                        cover
+                            .insert(Name::new("Tile Cover"))
+                            .insert(BoardSprite::CoveredTile);
+                        if game.is_flagged(coordinates) {
+                            cover
+                                .insert(Flagged)
+                                .with_children(|parent| systems::mark::spawn_flag(parent, board_assets, size));
+                        }
+                        covered_tiles.insert(coordinates, cover.id());
+                    });
                 }
+                
+                Self::spawn_tile_content(&mut cmd, tile, size, padding, board_assets);
+            }
+        }
+    }
+
+    /// Inserts the components and spawns the sprites revealed under a tile cover
+    fn spawn_tile_content(
+        cmd: &mut EntityCommands,
+        tile: &Tile,
+        size: f32,
+        padding: f32,
+        board_assets: &BoardAssets,
+    ) {
+        match tile {
+            Tile::Bomb => {
+                cmd.insert(Bomb);
+                cmd.with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::splat(size - padding)),
+                                color: board_assets.bomb_material.color,
+                                ..Default::default()
+                            },
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            texture: board_assets.bomb_material.texture.clone(),
+                            ..Default::default()
+                        })
+                        .insert(BoardSprite::Bomb);
+                });
+            }
+            Tile::BombNeighbor(v) => {
+                cmd.insert(BombNeighbor{ count: *v });
+                cmd.with_children(|parent| {
+                    parent.spawn_bundle(Self::bomb_count_text_bundle(*v, board_assets, size - padding));
+                });
             }
+            Tile::Empty => ()
         }
     }
 
     fn adaptative_tile_size(window: Res<WindowDescriptor>, (min, max): (f32, f32), (width, height): (u16, u16)) -> f32 {
         let max_width = window.width / width as f32;
-        let max_height = window.height / height as f32;
+        let max_height = (window.height - HUD_HEIGHT).max(0.0) / height as f32;
 
         max_width.min(max_height).clamp(min, max)
     }
 
-    fn cleanup_board(board: Res<Board>, mut commands: Commands) {
+    fn cleanup_board(
+        board: Res<Board>,
+        replay_player: Option<Res<ReplayPlayer>>,
+        mut commands: Commands,
+    ) {
         commands.entity(board.entity).despawn_recursive();
         commands.remove_resource::<Board>();
+        commands.remove_resource::<GameStats>();
+        commands.remove_resource::<ReplayRecorder>();
+        // The playback only survives its own restarts
+        if !replay_player.is_some_and(|player| player.restarting) {
+            commands.remove_resource::<ReplayPlayer>();
+        }
     }
 }
\ No newline at end of file
@@ -1,15 +1,20 @@
 use crate::bounds::Bounds2;
-use crate::{Coordinates, TileMap};
+use crate::{BoardOptions, Coordinates};
 use bevy::prelude::*;
 use bevy::utils::HashMap;
+use minesweeper_core::Game;
 
+/// Rendered game. Must be used as a resource, the rules are left to `game`
 #[derive(Debug)]
 pub struct Board {
-    pub tile_map: TileMap,
+    pub game: Game,
+    /// Options the board was created with
+    pub options: BoardOptions,
     pub bounds: Bounds2,
     pub tile_size: f32,
+    /// Cover entity of every covered tile
     pub covered_tiles: HashMap<Coordinates, Entity>,
-    pub entity: Entity
+    pub entity: Entity,
 }
 
 impl Board {
@@ -27,20 +32,4 @@ impl Board {
             y: (coordinates.y / self.tile_size) as u16,
         })
     }
-
-    pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        self.covered_tiles.get(coords)
-    }
-
-    pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-        self.covered_tiles.remove(coords)
-    }
-
-    pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
-        self.tile_map
-            .safe_quare_at(coord)
-            .filter_map(|c| self.covered_tiles.get(&c))
-            .copied()
-            .collect()
-    }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use crate::components::BoardSprite;
 
 #[derive(Debug, Clone)]
 pub struct SpriteMaterial {
@@ -19,7 +21,8 @@ impl Default for SpriteMaterial {
 /// Assets for the board. Must be used as a resource.
 ///
 /// Use the loader for partial setup
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, TypeUuid)]
+#[uuid = "2a294d6f-0ad8-4aee-815d-70b5b026abb1"]
 pub struct BoardAssets {
     pub label: String,
     pub board_material: SpriteMaterial,
@@ -29,9 +32,56 @@ pub struct BoardAssets {
     pub bomb_counter_colors: Vec<Color>,
     pub flag_material: SpriteMaterial,
     pub bomb_material: SpriteMaterial,
+    /// Overlay of the tile picked by a hint
+    pub hint_material: SpriteMaterial,
+    /// Replaces the flags put on safe tiles once the game is lost
+    pub wrong_flag_material: SpriteMaterial,
+    /// Tile of the bomb that exploded
+    pub exploded_tile_material: SpriteMaterial,
 }
 
 impl BoardAssets {
+    /// Theme built into the game, for when no theme file can be loaded
+    pub fn builtin(asset_server: &AssetServer) -> Self {
+        Self {
+            label: "Built-in".to_string(),
+            board_material: SpriteMaterial {
+                color: Color::WHITE,
+                ..Default::default()
+            },
+            tile_material: SpriteMaterial {
+                color: Color::DARK_GRAY,
+                ..Default::default()
+            },
+            covered_tile_material: SpriteMaterial {
+                color: Color::GRAY,
+                ..Default::default()
+            },
+            bomb_counter_font: asset_server.load("fonts/pixeled.ttf"),
+            bomb_counter_colors: Self::default_colors(),
+            flag_material: SpriteMaterial {
+                texture: asset_server.load("sprites/flag.png"),
+                color: Color::WHITE,
+            },
+            bomb_material: SpriteMaterial {
+                texture: asset_server.load("sprites/bomb.png"),
+                color: Color::WHITE,
+            },
+            hint_material: SpriteMaterial {
+                color: Color::rgba(0.2, 0.8, 0.2, 0.5),
+                ..Default::default()
+            },
+            wrong_flag_material: SpriteMaterial {
+                texture: asset_server.load("sprites/wrong_flag.png"),
+                color: Color::WHITE,
+            },
+            exploded_tile_material: SpriteMaterial {
+                color: Color::RED,
+                ..Default::default()
+            },
+        }
+    }
+
     pub fn default_colors() -> Vec<Color> {
         vec![
             Color::WHITE,
@@ -42,6 +92,19 @@ impl BoardAssets {
         ]
     }
 
+    pub fn material(&self, sprite: BoardSprite) -> &SpriteMaterial {
+        match sprite {
+            BoardSprite::Board => &self.board_material,
+            BoardSprite::Tile => &self.tile_material,
+            BoardSprite::CoveredTile => &self.covered_tile_material,
+            BoardSprite::Flag => &self.flag_material,
+            BoardSprite::Bomb => &self.bomb_material,
+            BoardSprite::Hint => &self.hint_material,
+            BoardSprite::WrongFlag => &self.wrong_flag_material,
+            BoardSprite::ExplodedTile => &self.exploded_tile_material,
+        }
+    }
+
     pub fn bomb_counter_color(&self, counter: u8) -> Color {
         let counter = counter.saturating_sub(1) as usize;
         match self.bomb_counter_colors.get(counter) {
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+use bevy::asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use serde::{ Deserialize, Serialize };
+use crate::resources::{BoardAssets, SpriteMaterial};
+
+/// Material of a `.theme.ron` file, textures are relative to the assets folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialDescriptor {
+    #[serde(default = "MaterialDescriptor::default_color")]
+    pub color: Color,
+    #[serde(default)]
+    pub texture: Option<String>,
+}
+
+/// Content of a `.theme.ron` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeDescriptor {
+    pub label: String,
+    pub board: MaterialDescriptor,
+    pub tile: MaterialDescriptor,
+    pub covered_tile: MaterialDescriptor,
+    pub flag: MaterialDescriptor,
+    pub bomb: MaterialDescriptor,
+    pub hint: MaterialDescriptor,
+    pub wrong_flag: MaterialDescriptor,
+    pub exploded_tile: MaterialDescriptor,
+    pub bomb_counter_font: String,
+    #[serde(default = "BoardAssets::default_colors")]
+    pub bomb_counter_colors: Vec<Color>,
+}
+
+/// Loads `BoardAssets` from `.theme.ron` files
+#[derive(Debug, Default)]
+pub struct BoardAssetsLoader;
+
+impl MaterialDescriptor {
+    fn default_color() -> Color {
+        Color::WHITE
+    }
+
+    fn load(&self, load_context: &LoadContext, dependencies: &mut Vec<AssetPath<'static>>) -> SpriteMaterial {
+        let texture = match &self.texture {
+            Some(path) => {
+                let path = AssetPath::new(PathBuf::from(path), None);
+                dependencies.push(path.clone());
+                load_context.get_handle(path)
+            }
+            None => SpriteMaterial::default().texture,
+        };
+        SpriteMaterial {
+            color: self.color,
+            texture,
+        }
+    }
+}
+
+impl AssetLoader for BoardAssetsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let theme: ThemeDescriptor = ron::de::from_bytes(bytes)?;
+            let mut dependencies = Vec::new();
+
+            let font_path = AssetPath::new(PathBuf::from(&theme.bomb_counter_font), None);
+            dependencies.push(font_path.clone());
+            let board_assets = BoardAssets {
+                label: theme.label.clone(),
+                board_material: theme.board.load(load_context, &mut dependencies),
+                tile_material: theme.tile.load(load_context, &mut dependencies),
+                covered_tile_material: theme.covered_tile.load(load_context, &mut dependencies),
+                bomb_counter_font: load_context.get_handle(font_path),
+                bomb_counter_colors: theme.bomb_counter_colors.clone(),
+                flag_material: theme.flag.load(load_context, &mut dependencies),
+                bomb_material: theme.bomb.load(load_context, &mut dependencies),
+                hint_material: theme.hint.load(load_context, &mut dependencies),
+                wrong_flag_material: theme.wrong_flag.load(load_context, &mut dependencies),
+                exploded_tile_material: theme.exploded_tile.load(load_context, &mut dependencies),
+            };
+
+            load_context.set_default_asset(LoadedAsset::new(board_assets).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
\ No newline at end of file
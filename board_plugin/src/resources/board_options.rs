@@ -1,4 +1,5 @@
 use bevy::prelude::Vec3;
+use minesweeper_core::GameOptions;
 use serde::{ Deserialize, Serialize };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,12 +16,10 @@ pub enum BoardPosition {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoardOptions {
-    pub map_size: (u16, u16),
-    pub bomb_count: u16,
+    pub game: GameOptions,
     pub position: BoardPosition,
     pub tile_size: TileSize,
     pub tile_padding: f32,
-    pub safe_start: bool,
 }
 
 impl Default for TileSize {
@@ -41,12 +40,10 @@ impl Default for BoardPosition {
 impl Default for BoardOptions {
     fn default() -> Self {
         Self { 
-            map_size: (15, 15),
-            bomb_count: 30,
+            game: Default::default(),
             position: Default::default(),
             tile_size: Default::default(),
             tile_padding: 0.0,
-            safe_start: false
         }
     }
 }
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+use crate::resources::BoardAssets;
+
+/// Theme to apply to the board, loaded from a `.theme.ron` file. Must be used as a resource.
+///
+/// Once loaded it replaces the `BoardAssets` resource and the board is re-skinned in place
+#[derive(Debug, Clone)]
+pub struct BoardTheme {
+    pub handle: Handle<BoardAssets>,
+}
\ No newline at end of file
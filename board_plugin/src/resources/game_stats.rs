@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+/// Statistics of the running game. Must be used as a resource.
+#[derive(Debug, Clone, Default)]
+pub struct GameStats {
+    /// Hints requested by the player
+    pub hints: u32,
+    /// Time spent since the first click, stops when the game is over
+    pub elapsed: Duration,
+    /// Left clicks on the board
+    pub left_clicks: u32,
+    /// Right clicks on the board
+    pub right_clicks: u32,
+    /// Middle or left and right clicks on the board
+    pub chord_clicks: u32,
+}
+
+/// Efficiency metrics of a game, as judged by competitive players
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GameSummary {
+    /// 3BV of the whole board
+    pub bbbv: u32,
+    /// 3BV cleared by the player, equal to `bbbv` on a won game
+    pub solved_bbbv: u32,
+    /// Cleared 3BV per second
+    pub bbbv_per_second: f32,
+    /// Index of efficiency: cleared 3BV per click
+    pub ioe: f32,
+    pub clicks_per_second: f32,
+}
+
+impl GameStats {
+    pub fn clicks(&self) -> u32 {
+        self.left_clicks + self.right_clicks + self.chord_clicks
+    }
+
+    /// Metrics of the game, given the 3BV of the board and the cleared part of it
+    pub fn summary(&self, bbbv: u32, solved_bbbv: u32) -> GameSummary {
+        let per = |value: f32, total: f32| match total > 0.0 {
+            true => value / total,
+            false => 0.0,
+        };
+        let seconds = self.elapsed.as_secs_f32();
+        GameSummary {
+            bbbv,
+            solved_bbbv,
+            bbbv_per_second: per(solved_bbbv as f32, seconds),
+            ioe: per(solved_bbbv as f32, self.clicks() as f32),
+            clicks_per_second: per(self.clicks() as f32, seconds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_divides_by_time_and_clicks() {
+        let stats = GameStats {
+            elapsed: Duration::from_secs(10),
+            left_clicks: 15,
+            right_clicks: 3,
+            chord_clicks: 2,
+            ..Default::default()
+        };
+        let summary = stats.summary(30, 20);
+        assert_eq!(summary.bbbv, 30);
+        assert_eq!(summary.solved_bbbv, 20);
+        assert_eq!(summary.bbbv_per_second, 2.0);
+        assert_eq!(summary.ioe, 1.0);
+        assert_eq!(summary.clicks_per_second, 2.0);
+    }
+
+    #[test]
+    fn summary_of_an_empty_game_is_zero() {
+        let summary = GameStats::default().summary(12, 0);
+        assert_eq!(summary.bbbv, 12);
+        assert_eq!(summary.bbbv_per_second, 0.0);
+        assert_eq!(summary.ioe, 0.0);
+        assert_eq!(summary.clicks_per_second, 0.0);
+    }
+
+    #[test]
+    fn summary_without_time_or_clicks_stays_finite() {
+        // A safe start can clear part of the board before any click or tick
+        let summary = GameStats::default().summary(12, 5);
+        assert_eq!(summary.bbbv_per_second, 0.0);
+        assert_eq!(summary.ioe, 0.0);
+
+        let stats = GameStats { left_clicks: 4, ..Default::default() };
+        let summary = stats.summary(12, 8);
+        assert_eq!(summary.bbbv_per_second, 0.0);
+        assert_eq!(summary.ioe, 2.0);
+        assert_eq!(summary.clicks_per_second, 0.0);
+    }
+}
\ No newline at end of file
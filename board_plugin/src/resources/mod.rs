@@ -1,10 +1,20 @@
-pub (crate) mod tile_map;
-pub (crate) mod tile;
+pub use minesweeper_core::{BoardGeneration, Difficulty, GameOptions};
 
 pub use board_assets::*;
+pub use board_assets_loader::*;
 pub use board_options::*;
 pub use board::*;
+pub use board_theme::*;
+pub use game_stats::*;
+pub use replay::*;
+pub use saved_game::*;
 
 mod board_assets;
+mod board_assets_loader;
 mod board_options;
-mod board;
\ No newline at end of file
+mod board;
+mod board_theme;
+mod game_stats;
+mod replay;
+mod saved_game;
+mod versioned_file;
\ No newline at end of file
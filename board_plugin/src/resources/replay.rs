@@ -0,0 +1,255 @@
+use std::path::Path;
+use std::time::Duration;
+use serde::{ Deserialize, Serialize };
+use minesweeper_core::{Coordinates, TileMap};
+use crate::errors::VersionedFileError;
+use crate::resources::{Board, BoardOptions, SavedGame};
+use super::versioned_file::{load_versioned, save_versioned, VersionedFile};
+
+/// Version written in replays, bumped whenever `Replay` changes
+pub const REPLAY_VERSION: u32 = 2;
+
+const MIN_SPEED: f32 = 0.25;
+const MAX_SPEED: f32 = 16.0;
+
+/// Player input of a replay
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplayAction {
+    Trigger(Coordinates),
+    Mark(Coordinates),
+    Chord(Coordinates),
+}
+
+/// Input and the game time it happened at
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub time: Duration,
+    pub action: ReplayAction,
+}
+
+/// Inputs of the running game. Must be used as a resource.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    pub events: Vec<ReplayEvent>,
+}
+
+/// Recorded game with its mine layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub options: BoardOptions,
+    pub seed: u64,
+    pub tile_map: TileMap,
+    pub events: Vec<ReplayEvent>,
+}
+
+/// Plays a replay back by sending its inputs again. Must be used as a resource,
+/// player input is ignored while it exists
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    /// Playback position in game time
+    pub clock: Duration,
+    /// Index of the next event to send
+    pub next_event: usize,
+    pub speed: f32,
+    pub paused: bool,
+    /// Position to rebuild the board at, seeking backwards replays from the start
+    pub(crate) seek_back: Option<Duration>,
+    /// Set while the board is rebuilt, so the player survives the cleanup
+    pub(crate) restarting: bool,
+}
+
+impl Replay {
+    /// Replay of the inputs recorded on `board`, `None` until its bombs are placed
+    pub fn from_board(board: &Board, recorder: &ReplayRecorder) -> Option<Self> {
+        if !board.game.generated() {
+            return None;
+        }
+        Some(Self {
+            version: REPLAY_VERSION,
+            options: board.options.clone(),
+            seed: board.game.seed(),
+            tile_map: board.game.tile_map().clone(),
+            events: recorder.events.clone(),
+        })
+    }
+
+    /// Fully covered game with the recorded mine layout
+    pub fn saved_game(&self) -> SavedGame {
+        let mut options = self.options.clone();
+        // The safe start only happened if the bombs were placed with the board
+        if options.game.generation.is_deferred() {
+            options.game.safe_start = false;
+        }
+        SavedGame::new(options, self.seed, true, self.tile_map.clone())
+    }
+
+    /// Time of the last input
+    pub fn duration(&self) -> Duration {
+        self.events.last().map(|e| e.time).unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VersionedFileError> {
+        save_versioned(self, path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VersionedFileError> {
+        load_versioned(path)
+    }
+}
+
+impl VersionedFile for Replay {
+    const VERSION: u32 = REPLAY_VERSION;
+
+    fn is_valid(&self) -> bool {
+        let map = &self.tile_map;
+        (map.width(), map.height()) == self.options.game.map_size
+            && map.is_consistent()
+            && self.events.windows(2).all(|w| w[0].time <= w[1].time)
+            && self.events.iter().all(|e| match e.action {
+                ReplayAction::Trigger(c) | ReplayAction::Mark(c) | ReplayAction::Chord(c) => map.in_bounds(c),
+            })
+    }
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            clock: Duration::ZERO,
+            next_event: 0,
+            speed: 1.0,
+            paused: false,
+            seek_back: None,
+            restarting: false,
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Sets the playback speed multiplier, clamped between 0.25 and 16
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    /// Moves the playback to `time`. Going forward sends the skipped inputs
+    /// as fast as possible, going backward replays them from the start
+    pub fn seek(&mut self, time: Duration) {
+        let time = time.min(self.replay.duration());
+        if time < self.clock {
+            self.seek_back = Some(time);
+        } else {
+            self.clock = time;
+        }
+    }
+
+    /// Whether every input was sent
+    pub fn is_finished(&self) -> bool {
+        self.next_event >= self.replay.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(secs: u64, action: ReplayAction) -> ReplayEvent {
+        ReplayEvent { time: Duration::from_secs(secs), action }
+    }
+
+    fn coords(x: u16, y: u16) -> Coordinates {
+        Coordinates { x, y }
+    }
+
+    fn replay(events: Vec<ReplayEvent>) -> Replay {
+        let tile_map: TileMap = "*..\n...\n..*".parse().unwrap();
+        let mut options = BoardOptions::default();
+        options.game.map_size = (tile_map.width(), tile_map.height());
+        options.game.bomb_count = tile_map.bomb_count();
+        Replay { version: REPLAY_VERSION, options, seed: 0, tile_map, events }
+    }
+
+    fn player() -> ReplayPlayer {
+        ReplayPlayer::new(replay(vec![
+            event(1, ReplayAction::Trigger(coords(1, 1))),
+            event(4, ReplayAction::Mark(coords(0, 2))),
+            event(6, ReplayAction::Chord(coords(1, 1))),
+        ]))
+    }
+
+    #[test]
+    fn seeking_forward_moves_the_clock() {
+        let mut player = player();
+        player.seek(Duration::from_secs(3));
+        assert_eq!(player.clock, Duration::from_secs(3));
+        assert_eq!(player.seek_back, None);
+    }
+
+    #[test]
+    fn seeking_stops_at_the_last_input() {
+        let mut player = player();
+        player.seek(Duration::from_secs(60));
+        assert_eq!(player.clock, Duration::from_secs(6));
+    }
+
+    #[test]
+    fn seeking_backward_restarts_the_replay() {
+        let mut player = player();
+        player.seek(Duration::from_secs(5));
+        player.next_event = 2;
+        player.seek(Duration::from_secs(2));
+        // The board is rebuilt before the clock moves back
+        assert_eq!(player.clock, Duration::from_secs(5));
+        assert_eq!(player.seek_back, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn recorded_replays_are_valid() {
+        assert!(replay(Vec::new()).is_valid());
+        assert!(player().replay.is_valid());
+    }
+
+    #[test]
+    fn inputs_must_be_in_order() {
+        let replay = replay(vec![
+            event(4, ReplayAction::Trigger(coords(1, 1))),
+            event(1, ReplayAction::Mark(coords(0, 2))),
+        ]);
+        assert!(!replay.is_valid());
+    }
+
+    #[test]
+    fn inputs_must_be_on_the_board() {
+        assert!(!replay(vec![event(1, ReplayAction::Trigger(coords(3, 0)))]).is_valid());
+        assert!(!replay(vec![event(1, ReplayAction::Mark(coords(0, 3)))]).is_valid());
+        assert!(!replay(vec![event(1, ReplayAction::Chord(coords(3, 3)))]).is_valid());
+    }
+
+    #[test]
+    fn board_must_match_the_options() {
+        let mut replay = replay(Vec::new());
+        replay.options.game.map_size = (3, 4);
+        assert!(!replay.is_valid());
+    }
+
+    #[test]
+    fn files_of_other_versions_are_rejected() {
+        let path = std::env::temp_dir().join(format!("replay-version-{}.ron", std::process::id()));
+        let mut replay = player().replay;
+        replay.save(&path).unwrap();
+        let loaded = Replay::load(&path).unwrap();
+        assert_eq!(loaded.events, replay.events);
+
+        replay.version = REPLAY_VERSION + 1;
+        replay.save(&path).unwrap();
+        let result = Replay::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(VersionedFileError::UnsupportedVersion { version, expected: REPLAY_VERSION }) if version == REPLAY_VERSION + 1
+        ));
+    }
+}
\ No newline at end of file
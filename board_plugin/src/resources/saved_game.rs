@@ -0,0 +1,179 @@
+use std::path::Path;
+use std::time::Duration;
+use serde::{ Deserialize, Serialize };
+use minesweeper_core::{Coordinates, Game, TileMap};
+use crate::errors::VersionedFileError;
+use crate::resources::{Board, BoardOptions, GameStats, ReplayEvent, ReplayRecorder};
+use super::versioned_file::{load_versioned, save_versioned, VersionedFile};
+
+/// Version written in saved games, bumped whenever `SavedGame` changes
+pub const SAVE_VERSION: u32 = 2;
+
+/// Snapshot of a game in progress, restored by `create_board` when inserted as a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub version: u32,
+    pub options: BoardOptions,
+    pub seed: u64,
+    pub generated: bool,
+    pub tile_map: TileMap,
+    /// Covered tiles, flagged ones included
+    pub covered_tiles: Vec<Coordinates>,
+    pub flagged_tiles: Vec<Coordinates>,
+    pub hints: u32,
+    pub elapsed: Duration,
+    #[serde(default)]
+    pub left_clicks: u32,
+    #[serde(default)]
+    pub right_clicks: u32,
+    #[serde(default)]
+    pub chord_clicks: u32,
+    /// Inputs recorded so far, so the replay of a loaded game is complete
+    #[serde(default)]
+    pub replay: Vec<ReplayEvent>,
+}
+
+impl SavedGame {
+    /// A new game where every tile is covered
+    pub fn new(options: BoardOptions, seed: u64, generated: bool, tile_map: TileMap) -> Self {
+        let covered_tiles = (0..tile_map.height())
+            .flat_map(|y| (0..tile_map.width()).map(move |x| Coordinates { x, y }))
+            .collect();
+        Self {
+            version: SAVE_VERSION,
+            options,
+            seed,
+            generated,
+            tile_map,
+            covered_tiles,
+            flagged_tiles: Vec::new(),
+            hints: 0,
+            elapsed: Duration::ZERO,
+            left_clicks: 0,
+            right_clicks: 0,
+            chord_clicks: 0,
+            replay: Vec::new(),
+        }
+    }
+
+    pub fn from_board(board: &Board, game_stats: &GameStats, recorder: &ReplayRecorder) -> Self {
+        let game = &board.game;
+        let mut covered_tiles: Vec<_> = game.covered_tiles().collect();
+        covered_tiles.sort();
+        let mut flagged_tiles: Vec<_> = game.flagged_tiles().collect();
+        flagged_tiles.sort();
+        Self {
+            version: SAVE_VERSION,
+            options: board.options.clone(),
+            seed: game.seed(),
+            generated: game.generated(),
+            tile_map: game.tile_map().clone(),
+            covered_tiles,
+            flagged_tiles,
+            hints: game_stats.hints,
+            elapsed: game_stats.elapsed,
+            left_clicks: game_stats.left_clicks,
+            right_clicks: game_stats.right_clicks,
+            chord_clicks: game_stats.chord_clicks,
+            replay: recorder.events.clone(),
+        }
+    }
+
+    /// Game rules restored from the snapshot
+    pub fn game(&self) -> Game {
+        Game::restore(
+            self.tile_map.clone(),
+            self.options.game.bomb_count,
+            self.options.game.generation.clone(),
+            self.seed,
+            self.generated,
+            self.covered_tiles.iter().copied(),
+            self.flagged_tiles.iter().copied(),
+        )
+    }
+
+    pub fn replay_recorder(&self) -> ReplayRecorder {
+        ReplayRecorder {
+            events: self.replay.clone(),
+        }
+    }
+
+    pub fn game_stats(&self) -> GameStats {
+        GameStats {
+            hints: self.hints,
+            elapsed: self.elapsed,
+            left_clicks: self.left_clicks,
+            right_clicks: self.right_clicks,
+            chord_clicks: self.chord_clicks,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VersionedFileError> {
+        save_versioned(self, path)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VersionedFileError> {
+        load_versioned(path)
+    }
+}
+
+impl VersionedFile for SavedGame {
+    const VERSION: u32 = SAVE_VERSION;
+
+    fn is_valid(&self) -> bool {
+        let map = &self.tile_map;
+        (map.width(), map.height()) == self.options.game.map_size
+            && map.is_consistent()
+            && (self.generated || map.bomb_count() == 0)
+            && self.covered_tiles.iter().all(|c| map.in_bounds(*c))
+            && self.flagged_tiles.iter().all(|c| self.covered_tiles.contains(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved_game(board: &str, generated: bool) -> SavedGame {
+        let tile_map: TileMap = board.parse().unwrap();
+        let mut options = BoardOptions::default();
+        options.game.map_size = (tile_map.width(), tile_map.height());
+        options.game.bomb_count = tile_map.bomb_count();
+        SavedGame::new(options, 0, generated, tile_map)
+    }
+
+    #[test]
+    fn new_games_are_valid() {
+        assert!(saved_game("*..\n...\n..*", true).is_valid());
+        assert!(saved_game("...\n...", false).is_valid());
+    }
+
+    #[test]
+    fn map_must_match_the_options() {
+        let mut game = saved_game("*..\n...\n..*", true);
+        game.options.game.map_size = (4, 3);
+        assert!(!game.is_valid());
+    }
+
+    #[test]
+    fn ungenerated_maps_have_no_bombs() {
+        assert!(!saved_game("*..\n...\n..*", false).is_valid());
+    }
+
+    #[test]
+    fn tiles_must_be_on_the_map() {
+        let mut game = saved_game("*..\n...\n..*", true);
+        game.covered_tiles.push(Coordinates { x: 3, y: 0 });
+        assert!(!game.is_valid());
+    }
+
+    #[test]
+    fn flagged_tiles_must_be_covered() {
+        let mut game = saved_game("*..\n...\n..*", true);
+        game.flagged_tiles.push(Coordinates { x: 0, y: 2 });
+        assert!(game.is_valid());
+
+        game.covered_tiles.retain(|c| *c != Coordinates { x: 0, y: 2 });
+        assert!(!game.is_valid());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::fs;
+use std::path::Path;
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+use serde::{ Deserialize, Serialize };
+use crate::errors::VersionedFileError;
+
+/// RON file whose first field is its version, checked before the rest is parsed
+pub(crate) trait VersionedFile: Serialize + DeserializeOwned {
+    /// Version written in the files, bumped whenever the type changes
+    const VERSION: u32;
+
+    /// Whether the parsed content is consistent with itself
+    fn is_valid(&self) -> bool;
+}
+
+/// Leading part of a versioned file
+#[derive(Debug, Deserialize)]
+struct VersionHeader {
+    version: u32,
+}
+
+pub(crate) fn save_versioned<T: VersionedFile>(value: &T, path: impl AsRef<Path>) -> Result<(), VersionedFileError> {
+    let content = ron::ser::to_string_pretty(value, PrettyConfig::default())
+        .map_err(VersionedFileError::Parse)?;
+    fs::write(path, content).map_err(VersionedFileError::Io)
+}
+
+pub(crate) fn load_versioned<T: VersionedFile>(path: impl AsRef<Path>) -> Result<T, VersionedFileError> {
+    let content = fs::read_to_string(path).map_err(VersionedFileError::Io)?;
+    let header: VersionHeader = ron::from_str(&content).map_err(VersionedFileError::Parse)?;
+    if header.version != T::VERSION {
+        return Err(VersionedFileError::UnsupportedVersion { version: header.version, expected: T::VERSION });
+    }
+    let value: T = ron::from_str(&content).map_err(VersionedFileError::Parse)?;
+    match value.is_valid() {
+        true => Ok(value),
+        false => Err(VersionedFileError::Corrupted),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy::log;
+use minesweeper_core::Change;
+use crate::{Board, BoardAssets, BoardSprite, TileCoordinates};
+use crate::events::GameChangeEvent;
+
+/// Shows the end of a lost game: tints the detonated tile and crosses out the wrong flags,
+/// the remaining bombs are uncovered by `uncover_tiles`
+pub fn reveal_board(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+    mut game_change_evr: EventReader<GameChangeEvent>,
+    mut tiles: Query<(Entity, &TileCoordinates, &mut Sprite, &mut Handle<Image>)>,
+    children: Query<&Children>,
+) {
+    for change_event in game_change_evr.iter() {
+        match change_event.0 {
+            Change::Exploded(exploded) => {
+                for (entity, coords, mut sprite, mut texture) in tiles.iter_mut() {
+                    if coords.0 == exploded {
+                        sprite.color = board_assets.exploded_tile_material.color;
+                        *texture = board_assets.exploded_tile_material.texture.clone();
+                        commands.entity(entity).insert(BoardSprite::ExplodedTile);
+                    }
+                }
+            }
+            Change::WrongFlag(coords) => {
+                let entity = match board.covered_tiles.get(&coords) {
+                    Some(entity) => *entity,
+                    None => continue,
+                };
+                log::debug!("Wrong flag on {}", coords);
+                if let Ok(children) = children.get(entity) {
+                    for child in children.iter() {
+                        commands.entity(*child).despawn_recursive();
+                    }
+                }
+                commands.entity(entity).with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::splat(board.tile_size)),
+                                color: board_assets.wrong_flag_material.color,
+                                ..Default::default()
+                            },
+                            texture: board_assets.wrong_flag_material.texture.clone(),
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            ..Default::default()
+                        })
+                        .insert(Name::new("Wrong Flag"))
+                        .insert(BoardSprite::WrongFlag);
+                });
+            }
+            _ => (),
+        }
+    }
+}
\ No newline at end of file
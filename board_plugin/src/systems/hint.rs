@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy::log;
+use crate::{Board, BoardAssets, BoardSprite, Hint};
+use crate::events::HintRequestEvent;
+use crate::resources::GameStats;
+use minesweeper_core::solver::{self, BoardView};
+
+pub fn hint_request_handler(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+    mut game_stats: ResMut<GameStats>,
+    mut hint_request_evr: EventReader<HintRequestEvent>,
+    hints: Query<Entity, With<Hint>>,
+) {
+    if hint_request_evr.iter().count() == 0 || board.game.is_over() {
+        return;
+    }
+    // The bombs of a deferred board are placed around the first uncover
+    if !board.game.generated() {
+        log::info!("Hint: any tile is safe");
+        return;
+    }
+
+    // Flags are ignored as they may be wrong
+    let view = BoardView::from_tile_map(board.game.tile_map(), board.game.covered_tiles(), std::iter::empty());
+    let solution = solver::solve(&view);
+    let hint = solution
+        .probabilities
+        .iter()
+        .filter(|(coords, _)| !board.game.is_flagged(**coords))
+        .min_by(|(ca, pa), (cb, pb)| pa.total_cmp(pb).then(ca.cmp(cb)));
+    let (coords, probability) = match hint {
+        Some((coords, probability)) => (*coords, *probability),
+        None => return,
+    };
+    let entity = match board.covered_tiles.get(&coords) {
+        Some(entity) => *entity,
+        None => return,
+    };
+
+    for hint_entity in hints.iter() {
+        commands.entity(hint_entity).despawn_recursive();
+    }
+    log::info!("Hint: {} ({:.0}% bomb risk)", coords, probability * 100.0);
+    game_stats.hints += 1;
+
+    commands.entity(entity).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(board.tile_size - board.options.tile_padding)),
+                    color: board_assets.hint_material.color,
+                    ..Default::default()
+                },
+                texture: board_assets.hint_material.texture.clone(),
+                transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                ..Default::default()
+            })
+            .insert(Name::new("Hint"))
+            .insert(BoardSprite::Hint)
+            .insert(Hint);
+    });
+}
\ No newline at end of file
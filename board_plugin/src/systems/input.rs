@@ -1,39 +1,106 @@
 use crate::Board;
-use crate::events::TileTriggerEvent;
+use crate::resources::{GameStats, ReplayPlayer};
+use crate::events::{HintRequestEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use bevy::ecs::event::{Events, ManualEventReader};
 use bevy::input::{mouse::MouseButtonInput, ElementState};
 use bevy::{log, prelude::*};
 
+/// Left or right button held over the board. Clicks are sent on release,
+/// so pressing the other button meanwhile turns them into a single chord
+#[derive(Debug, Default)]
+pub struct BoardPress {
+    button: Option<MouseButton>,
+    chord: bool,
+}
+
+/// Mouse buttons read by `input_handling`. Must be used as a resource.
+#[derive(Default)]
+pub struct BoardInput {
+    reader: ManualEventReader<MouseButtonInput>,
+    press: BoardPress,
+}
+
+/// Skips the buttons used before the board was created, like the menu click starting the game
+pub fn reset_board_input(
+    button_events: Res<Events<MouseButtonInput>>,
+    mut board_input: ResMut<BoardInput>,
+) {
+    *board_input = BoardInput {
+        reader: button_events.get_reader_current(),
+        press: BoardPress::default(),
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn input_handling(
     windows: Res<Windows>,
     board: Res<Board>,
-    mut button_evr: EventReader<MouseButtonInput>,
-    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>
+    mut game_stats: ResMut<GameStats>,
+    replay_player: Option<Res<ReplayPlayer>>,
+    button_events: Res<Events<MouseButtonInput>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+    mut board_input: ResMut<BoardInput>,
 ) {
+    // Replays play the recorded inputs instead
+    if board.game.is_over() || replay_player.is_some() {
+        return;
+    }
     let window = windows.get_primary().unwrap();
+    let BoardInput { reader, press: board_press } = &mut *board_input;
 
-    for event in button_evr.iter() {
-        if let ElementState::Pressed = event.state {
-            let position = window.cursor_position();
-
-            if let Some(pos) = position {
-                log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
+    for event in reader.iter(&button_events) {
+        let button = match (event.button, event.state) {
+            (MouseButton::Middle, ElementState::Pressed) => MouseButton::Middle,
+            (MouseButton::Left | MouseButton::Right, ElementState::Pressed) => {
+                match board_press.button {
+                    Some(held) if held != event.button => board_press.chord = true,
+                    _ => *board_press = BoardPress { button: Some(event.button), chord: false },
+                }
+                continue;
+            }
+            // Releases without a press on the board, like the other button of a chord, are ignored
+            (MouseButton::Left | MouseButton::Right, ElementState::Released) => {
+                let press = std::mem::take(board_press);
+                match (press.button, press.chord) {
+                    (None, _) => continue,
+                    // Chords act like the middle button
+                    (Some(_), true) => MouseButton::Middle,
+                    (Some(_), false) => event.button,
+                }
+            }
+            _ => continue,
+        };
 
-                // log::info!("{:?}", tile_coordinates);
-                if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            // log::info!("Trying to uncover tile on {}", coordinates);
-                            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
-                        }
-                        MouseButton::Right => {
-                            // log::info!("Trying to mark tile on {}", coordinates);
-                            // TODO: Generate event
-                        }
-                        _ => (),
+        if let Some(pos) = window.cursor_position() {
+            log::trace!("Mouse button {:?} at {}", button, pos);
+            if let Some(coordinates) = board.mouse_position(window, pos) {
+                match button {
+                    MouseButton::Left => {
+                        game_stats.left_clicks += 1;
+                        tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+                    }
+                    MouseButton::Right => {
+                        game_stats.right_clicks += 1;
+                        tile_mark_ewr.send(TileMarkEvent(coordinates));
+                    }
+                    MouseButton::Middle => {
+                        game_stats.chord_clicks += 1;
+                        tile_chord_ewr.send(TileChordEvent(coordinates));
                     }
+                    _ => (),
                 }
             }
         }
     }
+}
+
+pub fn hint_input_handling(
+    keys: Res<Input<KeyCode>>,
+    mut hint_request_ewr: EventWriter<HintRequestEvent>,
+) {
+    if keys.just_pressed(KeyCode::H) {
+        hint_request_ewr.send(HintRequestEvent);
+    }
 }
\ No newline at end of file
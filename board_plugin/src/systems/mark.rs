@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy::log;
+use minesweeper_core::Change;
+use crate::{Board, BoardAssets, BoardSprite, Flagged};
+use crate::events::{GameChangeEvent, TileMarkEvent};
+
+pub fn mark_event_handler(
+    mut board: ResMut<Board>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut game_change_ewr: EventWriter<GameChangeEvent>,
+) {
+    for mark_event in tile_mark_evr.iter() {
+        for change in board.game.flag(mark_event.0) {
+            game_change_ewr.send(GameChangeEvent(change));
+        }
+    }
+}
+
+/// Adds or removes the flag sprites of the covers
+pub fn mark_tiles(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+    mut game_change_evr: EventReader<GameChangeEvent>,
+    children: Query<&Children>,
+) {
+    for change_event in game_change_evr.iter() {
+        let (coords, flagged) = match change_event.0 {
+            Change::Flagged(coords) => (coords, true),
+            Change::Unflagged(coords) => (coords, false),
+            _ => continue,
+        };
+        let entity = match board.covered_tiles.get(&coords) {
+            Some(entity) => *entity,
+            None => continue,
+        };
+
+        if flagged {
+            log::debug!("Flagged tile {}", coords);
+            commands
+                .entity(entity)
+                .insert(Flagged)
+                .with_children(|parent| spawn_flag(parent, &board_assets, board.tile_size));
+        } else {
+            log::debug!("Unflagged tile {}", coords);
+            commands.entity(entity).remove::<Flagged>();
+            if let Ok(children) = children.get(entity) {
+                for child in children.iter() {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// Spawns the flag sprite of a tile cover
+pub(crate) fn spawn_flag(parent: &mut ChildBuilder, board_assets: &BoardAssets, size: f32) {
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(size)),
+                color: board_assets.flag_material.color,
+                ..Default::default()
+            },
+            texture: board_assets.flag_material.texture.clone(),
+            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+            ..Default::default()
+        })
+        .insert(Name::new("Flag"))
+        .insert(BoardSprite::Flag);
+}
\ No newline at end of file
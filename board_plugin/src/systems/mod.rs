@@ -1,2 +1,10 @@
+pub mod game_over;
+pub mod hint;
 pub mod input;
+pub mod mark;
+pub mod pause;
+pub mod replay;
+pub mod save;
+pub mod theme;
+pub mod timer;
 pub mod uncover;
\ No newline at end of file
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy::log;
+use crate::{Board, BoardAssets, BoardSprite, PauseOverlay};
+
+/// Hides the board under an opaque overlay while the game is paused.
+/// Input and the game timer only run on update, so they are stopped as well.
+/// A finished board has nothing left to hide and stays visible
+pub fn pause_board(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+) {
+    log::info!("Game paused");
+    if board.game.is_over() {
+        return;
+    }
+    let size = board.bounds.size;
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(size),
+                    color: board_assets.covered_tile_material.color,
+                    ..Default::default()
+                },
+                texture: board_assets.covered_tile_material.texture.clone(),
+                // Above every tile, cover and flag
+                transform: Transform::from_xyz(size.x / 2.0, size.y / 2.0, 10.0),
+                ..Default::default()
+            })
+            .insert(Name::new("Pause Overlay"))
+            .insert(BoardSprite::CoveredTile)
+            .insert(PauseOverlay)
+            .with_children(|parent| {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text::with_section(
+                        "Paused",
+                        TextStyle {
+                            font: board_assets.bomb_counter_font.clone(),
+                            font_size: (size.x / 8.0).min(40.0),
+                            color: Color::WHITE,
+                        },
+                        TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                    ..Default::default()
+                });
+            });
+    });
+}
+
+pub fn resume_board(mut commands: Commands, overlays: Query<Entity, With<PauseOverlay>>) {
+    log::info!("Game resumed");
+    for entity in overlays.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
\ No newline at end of file
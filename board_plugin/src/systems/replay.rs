@@ -0,0 +1,132 @@
+use bevy::ecs::schedule::StateData;
+use bevy::prelude::*;
+use bevy::log;
+use crate::Board;
+use crate::events::{PlayReplayEvent, SaveReplayEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::resources::{GameStats, Replay, ReplayAction, ReplayEvent, ReplayPlayer, ReplayRecorder};
+
+/// Records the player inputs with the game time they happened at
+pub fn record_inputs(
+    game_stats: Res<GameStats>,
+    mut replay_recorder: ResMut<ReplayRecorder>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    let actions = tile_trigger_evr
+        .iter()
+        .map(|e| ReplayAction::Trigger(e.0))
+        .chain(tile_mark_evr.iter().map(|e| ReplayAction::Mark(e.0)))
+        .chain(tile_chord_evr.iter().map(|e| ReplayAction::Chord(e.0)));
+    for action in actions {
+        replay_recorder.events.push(ReplayEvent {
+            time: game_stats.elapsed,
+            action,
+        });
+    }
+}
+
+pub fn save_replay_handler(
+    board: Res<Board>,
+    replay_recorder: Res<ReplayRecorder>,
+    mut save_replay_evr: EventReader<SaveReplayEvent>,
+) {
+    for save_event in save_replay_evr.iter() {
+        let replay = match Replay::from_board(&board, &replay_recorder) {
+            Some(replay) => replay,
+            None => {
+                log::warn!("The game has not started, nothing to record");
+                continue;
+            }
+        };
+        match replay.save(&save_event.0) {
+            Ok(()) => log::info!("Saved replay to {}", save_event.0.display()),
+            Err(e) => log::error!("Failed to save replay: {}", e),
+        }
+    }
+}
+
+/// Reads the replay and restarts the running state on its board, `play_replay` then sends its inputs
+pub fn play_replay_handler<T: StateData>(
+    mut commands: Commands,
+    mut state: ResMut<State<T>>,
+    replay_player: Option<ResMut<ReplayPlayer>>,
+    mut play_replay_evr: EventReader<PlayReplayEvent>,
+) {
+    let play_event = match play_replay_evr.iter().last() {
+        Some(event) => event,
+        None => return,
+    };
+    let replay = match Replay::load(&play_event.0) {
+        Ok(replay) => replay,
+        Err(e) => {
+            log::error!("Failed to load replay: {}", e);
+            return;
+        }
+    };
+    log::info!("Playing replay {}", play_event.0.display());
+    commands.insert_resource(replay.saved_game());
+    let mut player = ReplayPlayer::new(replay);
+    player.restarting = true;
+    match replay_player {
+        Some(mut replay_player) => *replay_player = player,
+        None => commands.insert_resource(player),
+    }
+    if let Err(e) = state.restart() {
+        log::error!("Failed to restart the game: {}", e);
+    }
+}
+
+/// Advances the replay clock and sends the inputs that are due, one per frame
+#[allow(clippy::too_many_arguments)]
+pub fn play_replay<T: StateData>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut state: ResMut<State<T>>,
+    board: Res<Board>,
+    mut game_stats: ResMut<GameStats>,
+    replay_player: Option<ResMut<ReplayPlayer>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    let mut player = match replay_player {
+        Some(player) => player,
+        None => return,
+    };
+    // Waiting for the board to be rebuilt
+    if player.restarting {
+        return;
+    }
+
+    if let Some(target) = player.seek_back.take() {
+        commands.insert_resource(player.replay.saved_game());
+        player.restarting = true;
+        player.clock = target;
+        player.next_event = 0;
+        if let Err(e) = state.restart() {
+            log::error!("Failed to restart the replay: {}", e);
+        }
+        return;
+    }
+
+    if !player.paused && !player.is_finished() {
+        let delta = time.delta().mul_f32(player.speed);
+        player.clock += delta;
+    }
+    // The game timer follows the replay clock
+    if board.game.has_started() && !board.game.is_over() {
+        game_stats.elapsed = player.clock;
+    }
+
+    let event = match player.replay.events.get(player.next_event) {
+        Some(event) if event.time <= player.clock => *event,
+        _ => return,
+    };
+    player.next_event += 1;
+    match event.action {
+        ReplayAction::Trigger(coords) => tile_trigger_ewr.send(TileTriggerEvent(coords)),
+        ReplayAction::Mark(coords) => tile_mark_ewr.send(TileMarkEvent(coords)),
+        ReplayAction::Chord(coords) => tile_chord_ewr.send(TileChordEvent(coords)),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use bevy::ecs::schedule::StateData;
+use bevy::prelude::*;
+use bevy::log;
+use crate::Board;
+use crate::events::{LoadGameEvent, SaveGameEvent};
+use crate::resources::{GameStats, ReplayRecorder, SavedGame};
+
+pub fn save_game_handler(
+    board: Res<Board>,
+    game_stats: Res<GameStats>,
+    replay_recorder: Res<ReplayRecorder>,
+    mut save_game_evr: EventReader<SaveGameEvent>,
+) {
+    for save_event in save_game_evr.iter() {
+        if board.game.is_over() {
+            log::warn!("The game is over, nothing to save");
+            continue;
+        }
+        match SavedGame::from_board(&board, &game_stats, &replay_recorder).save(&save_event.0) {
+            Ok(()) => log::info!("Saved game to {}", save_event.0.display()),
+            Err(e) => log::error!("Failed to save game: {}", e),
+        }
+    }
+}
+
+/// Reads the saved game and restarts the running state, `create_board` then restores it
+pub fn load_game_handler<T: StateData>(
+    mut commands: Commands,
+    mut state: ResMut<State<T>>,
+    mut load_game_evr: EventReader<LoadGameEvent>,
+) {
+    let load_event = match load_game_evr.iter().last() {
+        Some(event) => event,
+        None => return,
+    };
+    match SavedGame::load(&load_event.0) {
+        Ok(saved_game) => {
+            log::info!("Loading game from {}", load_event.0.display());
+            commands.insert_resource(saved_game);
+            if let Err(e) = state.restart() {
+                log::error!("Failed to restart the game: {}", e);
+            }
+        }
+        Err(e) => log::error!("Failed to load game: {}", e),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use bevy::log;
+use bevy::text::FontAtlasSet;
+use crate::{BoardAssets, BombNeighbor};
+use crate::components::BoardSprite;
+use crate::resources::BoardTheme;
+
+/// Replaces the `BoardAssets` resource once the `BoardTheme` is loaded
+pub fn apply_board_theme(
+    mut commands: Commands,
+    board_theme: Option<Res<BoardTheme>>,
+    themes: Res<Assets<BoardAssets>>,
+    board_assets: Option<ResMut<BoardAssets>>,
+    mut asset_evr: EventReader<AssetEvent<BoardAssets>>,
+) {
+    let board_theme = match board_theme {
+        Some(theme) => theme,
+        None => return,
+    };
+    let loaded = asset_evr.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => *handle == board_theme.handle,
+        AssetEvent::Removed { .. } => false,
+    });
+    if !loaded && !board_theme.is_changed() {
+        return;
+    }
+
+    // Not loaded yet, the creation event will bring us back here
+    let theme = match themes.get(&board_theme.handle) {
+        Some(theme) => theme.clone(),
+        None => return,
+    };
+    log::info!("Applying {} theme", theme.label);
+    match board_assets {
+        Some(mut board_assets) => *board_assets = theme,
+        None => commands.insert_resource(theme),
+    }
+}
+
+/// Applies the `BoardAssets` materials to the existing board when they change
+pub fn reskin_board(
+    board_assets: Res<BoardAssets>,
+    mut sprites: Query<(&BoardSprite, &mut Sprite, &mut Handle<Image>)>,
+    mut texts: Query<(&Parent, &mut Text)>,
+    bomb_neighbors: Query<&BombNeighbor>,
+) {
+    if !board_assets.is_changed() || board_assets.is_added() {
+        return;
+    }
+
+    for (board_sprite, mut sprite, mut texture) in sprites.iter_mut() {
+        let material = board_assets.material(*board_sprite);
+        sprite.color = material.color;
+        *texture = material.texture.clone();
+    }
+    for (parent, mut text) in texts.iter_mut() {
+        if let Ok(bomb_neighbor) = bomb_neighbors.get(parent.0) {
+            for section in text.sections.iter_mut() {
+                section.style.font = board_assets.bomb_counter_font.clone();
+                section.style.color = board_assets.bomb_counter_color(bomb_neighbor.count);
+            }
+        }
+    }
+}
+
+/// Redraws the texts using a font modified on disk.
+/// Sprite textures are reloaded in place by bevy, fonts need their glyph atlases rebuilt
+pub fn reload_fonts(
+    mut font_evr: EventReader<AssetEvent<Font>>,
+    mut font_atlas_sets: ResMut<Assets<FontAtlasSet>>,
+    mut texts: Query<&mut Text>,
+) {
+    for event in font_evr.iter() {
+        let handle = match event {
+            AssetEvent::Modified { handle } => handle,
+            AssetEvent::Created { .. } | AssetEvent::Removed { .. } => continue,
+        };
+        log::info!("Reloading font {:?}", handle.id);
+        font_atlas_sets.remove(handle.as_weak::<FontAtlasSet>());
+        for mut text in texts.iter_mut() {
+            if text.sections.iter().any(|section| section.style.font == *handle) {
+                text.set_changed();
+            }
+        }
+    }
+}
\ No newline at end of file
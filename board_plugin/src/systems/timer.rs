@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use crate::Board;
+use crate::resources::{GameStats, ReplayPlayer};
+
+/// Counts the game time from the first player click until the game is over,
+/// tiles uncovered by a safe start don't start it. Replays drive the game time themselves
+pub fn tick_game_timer(
+    time: Res<Time>,
+    board: Res<Board>,
+    replay_player: Option<Res<ReplayPlayer>>,
+    mut game_stats: ResMut<GameStats>,
+) {
+    if replay_player.is_none() && game_stats.clicks() > 0 && !board.game.is_over() {
+        game_stats.elapsed += time.delta();
+    }
+}
\ No newline at end of file
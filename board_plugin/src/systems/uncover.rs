@@ -1,51 +1,59 @@
 use bevy::prelude::*;
 use bevy::log;
-use crate::{Board, Bomb, BombNeighbor, Coordinates};
-use crate::components::Uncover;
-use crate::events::TileTriggerEvent;
+use minesweeper_core::Change;
+use crate::Board;
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, GameChangeEvent, TileChordEvent, TileTriggerEvent};
 
 pub fn trigger_event_handler(
-    mut commands: Commands,
-    board: Res<Board>,
-    mut tile_trigger_evr: EventReader<TileTriggerEvent>
+    mut board: ResMut<Board>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut game_change_ewr: EventWriter<GameChangeEvent>,
 ) {
     for trigger_event in tile_trigger_evr.iter() {
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
+        for change in board.game.reveal(trigger_event.0) {
+            game_change_ewr.send(GameChangeEvent(change));
         }
     }
 }
 
+pub fn chord_event_handler(
+    mut board: ResMut<Board>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut game_change_ewr: EventWriter<GameChangeEvent>,
+) {
+    for chord_event in tile_chord_evr.iter() {
+        for change in board.game.chord(chord_event.0) {
+            game_change_ewr.send(GameChangeEvent(change));
+        }
+    }
+}
+
+/// Removes the covers of the revealed tiles and announces the end of the game
 pub fn uncover_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    mut game_change_evr: EventReader<GameChangeEvent>,
+    mut board_completed_ewr: EventWriter<BoardCompletedEvent>,
+    mut bomb_explosion_ewr: EventWriter<BombExplosionEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        commands
-            .entity(entity)
-            .despawn_recursive();
-    
-        let (coords, bomb, bomb_counter) = match parents.get(parent.0) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("{}", e);
-                continue;
+    for change_event in game_change_evr.iter() {
+        match change_event.0 {
+            Change::Revealed(coords) => match board.covered_tiles.remove(&coords) {
+                Some(entity) => {
+                    log::debug!("Uncovered tile {} (entity {:?})", coords, entity);
+                    commands.entity(entity).despawn_recursive();
+                }
+                None => log::debug!("Tried to uncover an already uncovered tile"),
+            },
+            Change::Exploded(coords) => {
+                log::info!("BOOM!");
+                bomb_explosion_ewr.send(BombExplosionEvent(coords));
             }
-        };
-
-        match board.try_uncover_tile(coords) {
-            Some(e) => log::debug!("Uncovered tile {} (entity {:?})", coords, e),
-            None => log::debug!("Tried to uncover an already uncovered tile"),
-        }
-
-        if bomb.is_some() {
-            log::info!("BOOM!");
-        } else if bomb_counter.is_none() {
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
+            Change::Won => {
+                log::info!("Board completed");
+                board_completed_ewr.send(BoardCompletedEvent);
             }
+            _ => (),
         }
     }
 }
\ No newline at end of file
@@ -1,9 +1,8 @@
 use std::fmt::{ self, Display, Formatter };
 use std::ops::{ Add, Sub };
-use bevy::prelude::Component;
+use serde::{ Deserialize, Serialize };
 
-#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
@@ -24,8 +23,9 @@ impl Add<(i8, i8)> for Coordinates {
     type Output = Self;
 
     fn add(self, (x, y): (i8, i8)) -> Self::Output {
-        let x = ((self.x as i16) + x as i16) as u16;
-        let y = ((self.y as i16) + y as i16) as u16;
+        // Wraps below zero, so the result is out of bounds of any map
+        let x = self.x.wrapping_add_signed(x as i16);
+        let y = self.y.wrapping_add_signed(y as i16);
         Self { x, y }
     }
 }
@@ -0,0 +1,64 @@
+use crate::GameOptions;
+use serde::{ Deserialize, Serialize };
+
+/// Board size and bomb count presets
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// 9x9 with 10 bombs
+    Beginner,
+    /// 16x16 with 40 bombs
+    Intermediate,
+    /// 30x16 with 99 bombs
+    Expert,
+    Custom { width: u16, height: u16, mines: u16 },
+}
+
+impl Difficulty {
+    pub const PRESETS: [Self; 3] = [Self::Beginner, Self::Intermediate, Self::Expert];
+
+    /// Matching preset, or a custom difficulty
+    pub fn new(width: u16, height: u16, mines: u16) -> Self {
+        Self::PRESETS
+            .into_iter()
+            .find(|d| d.map_size() == (width, height) && d.bomb_count() == mines)
+            .unwrap_or(Self::Custom { width, height, mines })
+    }
+
+    pub const fn map_size(&self) -> (u16, u16) {
+        match self {
+            Self::Beginner => (9, 9),
+            Self::Intermediate => (16, 16),
+            Self::Expert => (30, 16),
+            Self::Custom { width, height, .. } => (*width, *height),
+        }
+    }
+
+    pub const fn bomb_count(&self) -> u16 {
+        match self {
+            Self::Beginner => 10,
+            Self::Intermediate => 40,
+            Self::Expert => 99,
+            Self::Custom { mines, .. } => *mines,
+        }
+    }
+}
+
+impl From<Difficulty> for GameOptions {
+    fn from(difficulty: Difficulty) -> Self {
+        let mut options = Self::default();
+        options.set_difficulty(difficulty);
+        options
+    }
+}
+
+impl GameOptions {
+    /// Changes the map size and bomb count, keeping the other options
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.map_size = difficulty.map_size();
+        self.bomb_count = difficulty.bomb_count();
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::new(self.map_size.0, self.map_size.1, self.bomb_count)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::io;
+use crate::Coordinates;
+use crate::settings::SETTINGS_PATH;
+
+/// Errors raised while generating a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardError {
+    /// The map has no tile
+    EmptyMap { width: u16, height: u16 },
+    /// The requested bomb count leaves no safe tile on the map
+    TooManyBombs { bomb_count: u16, tile_count: u32 },
+    /// No board solvable without guessing was found within the attempt budget
+    NoGuessAttemptsExhausted { attempts: u32 },
+    /// No-guess generation was given no attempt to place the bombs
+    NoGuessWithoutAttempts,
+    /// A fixed bomb lies outside of the map
+    BombOutOfBounds { coordinates: Coordinates },
+}
+
+impl Display for BoardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMap { width, height } => write!(f, "a {}x{} map has no tile", width, height),
+            Self::TooManyBombs { bomb_count, tile_count } => write!(
+                f,
+                "cannot place {} bombs on a map of {} tiles",
+                bomb_count, tile_count
+            ),
+            Self::NoGuessAttemptsExhausted { attempts } => write!(
+                f,
+                "no board solvable without guessing found in {} attempts",
+                attempts
+            ),
+            Self::NoGuessWithoutAttempts => write!(f, "no-guess generation needs at least one attempt"),
+            Self::BombOutOfBounds { coordinates } => write!(f, "bomb {} is outside of the map", coordinates),
+        }
+    }
+}
+
+impl Error for BoardError {}
+
+/// Errors raised while reading or writing an `.mbf` board
+#[derive(Debug)]
+pub enum MbfError {
+    Io(io::Error),
+    /// The file ends before the announced mines
+    Truncated { length: usize, expected: usize },
+    /// The format stores each dimension on a single byte
+    TooLarge { width: u16, height: u16 },
+    InvalidBoard(BoardError),
+}
+
+impl Display for MbfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not access the mbf board: {}", e),
+            Self::Truncated { length, expected } => write!(
+                f,
+                "truncated mbf board: {} bytes instead of {}",
+                length, expected
+            ),
+            Self::TooLarge { width, height } => write!(
+                f,
+                "a {}x{} board does not fit the mbf format, limited to 255x255",
+                width, height
+            ),
+            Self::InvalidBoard(e) => write!(f, "invalid mbf board: {}", e),
+        }
+    }
+}
+
+impl Error for MbfError {}
+
+/// Errors raised while reading a plain text board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TileMapParseError {
+    /// No row was found
+    Empty,
+    InvalidHeader(String),
+    TooLarge { width: usize, height: usize },
+    UnevenRow { row: usize, length: usize, expected: usize },
+    InvalidTile { row: usize, column: usize, character: char },
+    /// The header doesn't describe the grid, as (width, height, mines)
+    HeaderMismatch { expected: (u16, u16, usize), found: (u16, u16, usize) },
+    InvalidBoard(BoardError),
+}
+
+impl Display for TileMapParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the board has no rows"),
+            Self::InvalidHeader(header) => write!(
+                f,
+                "invalid header {:?}, expected <width>x<height> <mines>",
+                header
+            ),
+            Self::TooLarge { width, height } => write!(f, "a {}x{} board is too large", width, height),
+            Self::UnevenRow { row, length, expected } => write!(
+                f,
+                "row {} has {} tiles instead of {}",
+                row, length, expected
+            ),
+            Self::InvalidTile { row, column, character } => write!(
+                f,
+                "unexpected {:?} at row {}, column {}, expected '*' or '.'",
+                character, row, column
+            ),
+            Self::HeaderMismatch { expected, found } => write!(
+                f,
+                "the header announces a {}x{} board with {} mines, found {}x{} with {}",
+                expected.0, expected.1, expected.2, found.0, found.1, found.2
+            ),
+            Self::InvalidBoard(e) => write!(f, "invalid board: {}", e),
+        }
+    }
+}
+
+impl Error for TileMapParseError {}
+
+
+/// Errors raised while reading or writing the settings
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(io::Error),
+    Parse(ron::Error),
+    /// The options describe a board that can't be played
+    Invalid(BoardError),
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not access {}: {}", SETTINGS_PATH, e),
+            Self::Parse(e) => write!(f, "malformed {}: {}", SETTINGS_PATH, e),
+            Self::Invalid(e) => write!(f, "invalid options in {}: {}", SETTINGS_PATH, e),
+        }
+    }
+}
+
+impl Error for SettingsError {}
\ No newline at end of file
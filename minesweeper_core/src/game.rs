@@ -0,0 +1,418 @@
+use std::collections::HashSet;
+use serde::{ Deserialize, Serialize };
+use crate::generation::{self, BoardGeneration};
+use crate::{Coordinates, Tile, TileMap};
+use crate::errors::BoardError;
+
+/// Progress of a game
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameState {
+    Playing,
+    /// Only bombs remain covered
+    Won,
+    /// A bomb was uncovered
+    Lost,
+}
+
+/// Single consequence of a player action, for frontends to render
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Change {
+    /// The bombs were placed, on the first uncover of a deferred board
+    Generated,
+    Revealed(Coordinates),
+    Flagged(Coordinates),
+    Unflagged(Coordinates),
+    /// The revealed bomb that lost the game
+    Exploded(Coordinates),
+    /// Flag on a safe tile, shown once the game is lost
+    WrongFlag(Coordinates),
+    Won,
+}
+
+/// Rules of a minesweeper game: which tiles are covered or flagged and what
+/// revealing, flagging and chording them does
+#[derive(Debug, Clone)]
+pub struct Game {
+    tile_map: TileMap,
+    /// Bombs to place, the map holds none until the game is generated
+    bomb_count: u16,
+    generation: BoardGeneration,
+    /// Seed used to place the bombs, playing it again with the same generation gives the same board
+    seed: u64,
+    generated: bool,
+    covered: HashSet<Coordinates>,
+    flagged: HashSet<Coordinates>,
+    state: GameState,
+}
+
+impl Game {
+    /// Game with every tile covered, its bombs are placed now unless generation is deferred.
+    /// Fails when the map has no tile or the bombs don't fit on it
+    pub fn new(
+        map_size: (u16, u16),
+        bomb_count: u16,
+        generation: BoardGeneration,
+        seed: u64,
+    ) -> Result<Self, BoardError> {
+        if map_size.0 == 0 || map_size.1 == 0 {
+            return Err(BoardError::EmptyMap {
+                width: map_size.0,
+                height: map_size.1,
+            });
+        }
+        // Deferred bombs are placed on the first uncover, which must not fail
+        if generation.is_deferred() {
+            generation.check_bomb_count(map_size, bomb_count)?;
+        }
+        let (tile_map, generated) = match generation::generate(map_size, bomb_count, &generation, seed)? {
+            Some(tile_map) => (tile_map, true),
+            None => (TileMap::empty(map_size.0, map_size.1), false),
+        };
+        let covered = Self::all_coordinates(&tile_map).collect();
+        Ok(Self {
+            bomb_count: if generated { tile_map.bomb_count() } else { bomb_count },
+            tile_map,
+            generation,
+            seed,
+            generated,
+            covered,
+            flagged: HashSet::new(),
+            state: GameState::Playing,
+        })
+    }
+
+    /// Game in progress, its state is deduced from the covered tiles
+    pub fn restore(
+        tile_map: TileMap,
+        bomb_count: u16,
+        generation: BoardGeneration,
+        seed: u64,
+        generated: bool,
+        covered: impl IntoIterator<Item = Coordinates>,
+        flagged: impl IntoIterator<Item = Coordinates>,
+    ) -> Self {
+        let mut game = Self {
+            bomb_count: if generated { tile_map.bomb_count() } else { bomb_count },
+            tile_map,
+            generation,
+            seed,
+            generated,
+            covered: covered.into_iter().collect(),
+            flagged: flagged.into_iter().collect(),
+            state: GameState::Playing,
+        };
+        game.flagged.retain(|c| game.covered.contains(c));
+        let exploded = Self::all_coordinates(&game.tile_map)
+            .any(|c| game.tile_map.is_bomb_at(c) && !game.covered.contains(&c));
+        if exploded {
+            game.state = GameState::Lost;
+        } else if game.is_completed() {
+            game.state = GameState::Won;
+        }
+        game
+    }
+
+    /// Uncovers a covered tile that isn't flagged, with the whole area around it
+    /// when it has no bomb neighbor. The first uncover of a deferred board places the bombs
+    pub fn reveal(&mut self, coordinates: Coordinates) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if self.is_over() || !self.is_covered(coordinates) || self.is_flagged(coordinates) {
+            return changes;
+        }
+        if !self.generated {
+            let generated =
+                generation::generate_around(&mut self.tile_map, self.bomb_count, &self.generation, coordinates, self.seed);
+            // Only restored games can hold a bomb count `new` would have refused
+            if let Err(e) = generated {
+                log::error!("{}", e);
+                return changes;
+            }
+            self.generated = true;
+            self.bomb_count = self.tile_map.bomb_count();
+            changes.push(Change::Generated);
+        }
+
+        let mut stack = vec![coordinates];
+        while let Some(coords) = stack.pop() {
+            if !self.covered.remove(&coords) {
+                continue;
+            }
+            changes.push(Change::Revealed(coords));
+            match self.tile_at(coords) {
+                Tile::Bomb => {
+                    self.lose(coords, &mut changes);
+                    return changes;
+                }
+                Tile::Empty => stack.extend(
+                    self.tile_map
+                        .safe_quare_at(coords)
+                        .filter(|c| self.is_covered(*c) && !self.is_flagged(*c)),
+                ),
+                Tile::BombNeighbor(_) => (),
+            }
+        }
+
+        if self.is_completed() {
+            self.state = GameState::Won;
+            changes.push(Change::Won);
+        }
+        changes
+    }
+
+    /// Reveals the first empty tile of a fresh board, so the game doesn't start with a guess.
+    /// Deferred boards don't need it, their first uncover is always safe
+    pub fn safe_start(&mut self) -> Vec<Change> {
+        if !self.generated || self.has_started() {
+            return Vec::new();
+        }
+        let start = Self::all_coordinates(&self.tile_map).find(|c| self.tile_at(*c) == Tile::Empty);
+        match start {
+            Some(coordinates) => self.reveal(coordinates),
+            None => Vec::new(),
+        }
+    }
+
+    /// Flags or unflags a covered tile
+    pub fn flag(&mut self, coordinates: Coordinates) -> Vec<Change> {
+        if self.is_over() || !self.is_covered(coordinates) {
+            return Vec::new();
+        }
+        if self.flagged.remove(&coordinates) {
+            vec![Change::Unflagged(coordinates)]
+        } else {
+            self.flagged.insert(coordinates);
+            vec![Change::Flagged(coordinates)]
+        }
+    }
+
+    /// Reveals the unflagged neighbors of an uncovered number once as many flags surround it.
+    /// A misplaced flag leaves a bomb among them, which loses the game
+    pub fn chord(&mut self, coordinates: Coordinates) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if !self.tile_map.in_bounds(coordinates) || self.is_covered(coordinates) {
+            return changes;
+        }
+        let count = self.tile_map.bomb_count_at(coordinates);
+        if count == 0 || self.adjacent_flag_count(coordinates) != count {
+            return changes;
+        }
+        let neighbors: Vec<Coordinates> = self.tile_map.safe_quare_at(coordinates).collect();
+        for neighbor in neighbors {
+            changes.extend(self.reveal(neighbor));
+        }
+        changes
+    }
+
+    pub fn tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+
+    pub fn generation(&self) -> &BoardGeneration {
+        &self.generation
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Whether the bombs are placed, false until the first uncover for deferred boards
+    pub fn generated(&self) -> bool {
+        self.generated
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /// Set once the game is won or lost, actions are ignored afterwards
+    pub fn is_over(&self) -> bool {
+        self.state != GameState::Playing
+    }
+
+    pub fn is_covered(&self, coordinates: Coordinates) -> bool {
+        self.covered.contains(&coordinates)
+    }
+
+    pub fn is_flagged(&self, coordinates: Coordinates) -> bool {
+        self.flagged.contains(&coordinates)
+    }
+
+    /// Covered tiles, flagged ones included
+    pub fn covered_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.covered.iter().copied()
+    }
+
+    pub fn flagged_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.flagged.iter().copied()
+    }
+
+    pub fn adjacent_flag_count(&self, coordinates: Coordinates) -> u8 {
+        self.tile_map
+            .safe_quare_at(coordinates)
+            .filter(|c| self.is_flagged(*c))
+            .count() as u8
+    }
+
+    /// Bombs of the board, the configured count until they are placed
+    pub fn bomb_count(&self) -> u16 {
+        self.bomb_count
+    }
+
+    /// Bombs left to flag, negative when there are more flags than bombs
+    pub fn remaining_bombs(&self) -> i32 {
+        self.bomb_count as i32 - self.flagged.len() as i32
+    }
+
+    /// Whether a tile was uncovered yet
+    pub fn has_started(&self) -> bool {
+        self.covered.len() < self.tile_map.tile_count() as usize
+    }
+
+    /// 3BV cleared so far, zero until the bombs are placed
+    pub fn solved_bbbv(&self) -> u32 {
+        self.tile_map.solved_bbbv(|c| !self.is_covered(c))
+    }
+
+    /// The board is completed when only bombs remain covered
+    fn is_completed(&self) -> bool {
+        self.generated && self.tile_map.bomb_count() as usize == self.covered.len()
+    }
+
+    /// Uncovers every other bomb that isn't flagged and reports the wrong flags
+    fn lose(&mut self, exploded: Coordinates, changes: &mut Vec<Change>) {
+        self.state = GameState::Lost;
+        changes.push(Change::Exploded(exploded));
+        let mut covered: Vec<Coordinates> = self.covered.iter().copied().collect();
+        covered.sort();
+        for coords in covered {
+            match (self.tile_map.is_bomb_at(coords), self.is_flagged(coords)) {
+                (true, false) => {
+                    self.covered.remove(&coords);
+                    changes.push(Change::Revealed(coords));
+                }
+                (false, true) => changes.push(Change::WrongFlag(coords)),
+                _ => (),
+            }
+        }
+    }
+
+    fn tile_at(&self, coordinates: Coordinates) -> Tile {
+        self.tile_map[coordinates.y as usize][coordinates.x as usize]
+    }
+
+    fn all_coordinates(tile_map: &TileMap) -> impl Iterator<Item = Coordinates> {
+        let (width, height) = (tile_map.width(), tile_map.height());
+        (0..height).flat_map(move |y| (0..width).map(move |x| Coordinates { x, y }))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(x: u16, y: u16) -> Coordinates {
+        Coordinates { x, y }
+    }
+
+    /// Fully covered game of a text board:
+    ///
+    /// ```text
+    /// *1..
+    /// 1111
+    /// ..1*
+    /// ```
+    fn game() -> Game {
+        let tile_map: TileMap = "*...\n....\n...*".parse().unwrap();
+        let covered: Vec<Coordinates> = Game::all_coordinates(&tile_map).collect();
+        Game::restore(tile_map, 2, BoardGeneration::Immediate, 0, true, covered, [])
+    }
+
+    fn revealed(changes: &[Change]) -> Vec<Coordinates> {
+        let mut revealed: Vec<Coordinates> = changes
+            .iter()
+            .filter_map(|change| match change {
+                Change::Revealed(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        revealed.sort();
+        revealed
+    }
+
+    #[test]
+    fn reveal_opens_empty_areas() {
+        let mut game = game();
+        assert_eq!(revealed(&game.reveal(coords(1, 2))), vec![coords(1, 2)]);
+        let changes = game.reveal(coords(0, 0));
+        assert_eq!(
+            revealed(&changes),
+            vec![coords(0, 0), coords(0, 1), coords(1, 0), coords(1, 1), coords(2, 0), coords(2, 1)]
+        );
+        assert_eq!(changes.len(), 6);
+        assert!(game.reveal(coords(0, 0)).is_empty());
+        assert_eq!(game.state(), GameState::Playing);
+        // The number next to the top opening doesn't clear it
+        assert_eq!(game.solved_bbbv(), 1);
+    }
+
+    #[test]
+    fn flag_toggles_covered_tiles() {
+        let mut game = game();
+        assert_eq!(game.flag(coords(0, 2)), vec![Change::Flagged(coords(0, 2))]);
+        assert_eq!(game.remaining_bombs(), 1);
+        // Flagged tiles can't be revealed
+        assert!(game.reveal(coords(0, 2)).is_empty());
+        assert_eq!(game.flag(coords(0, 2)), vec![Change::Unflagged(coords(0, 2))]);
+        game.reveal(coords(1, 2));
+        assert!(game.flag(coords(1, 2)).is_empty());
+    }
+
+    #[test]
+    fn chord_reveals_neighbors_and_wins() {
+        let mut game = game();
+        game.reveal(coords(1, 2));
+        // Not enough flags around the number yet
+        assert!(game.chord(coords(1, 2)).is_empty());
+        game.flag(coords(0, 2));
+        let changes = game.chord(coords(1, 2));
+        assert_eq!(
+            revealed(&changes),
+            vec![coords(0, 1), coords(1, 1), coords(2, 1), coords(2, 2), coords(3, 1), coords(3, 2)]
+        );
+        assert_eq!(game.state(), GameState::Playing);
+
+        let changes = game.reveal(coords(0, 0));
+        assert_eq!(changes.last(), Some(&Change::Won));
+        assert_eq!(game.state(), GameState::Won);
+    }
+
+    #[test]
+    fn chord_on_a_wrong_flag_loses() {
+        let mut game = game();
+        game.reveal(coords(1, 2));
+        game.flag(coords(2, 2));
+        let changes = game.chord(coords(1, 2));
+        assert!(changes.contains(&Change::Exploded(coords(0, 2))));
+        assert!(changes.contains(&Change::Revealed(coords(3, 0))));
+        assert!(changes.contains(&Change::WrongFlag(coords(2, 2))));
+        assert_eq!(game.state(), GameState::Lost);
+    }
+
+    #[test]
+    fn revealing_a_bomb_loses() {
+        let mut game = game();
+        let changes = game.reveal(coords(3, 0));
+        assert_eq!(
+            changes,
+            vec![
+                Change::Revealed(coords(3, 0)),
+                Change::Exploded(coords(3, 0)),
+                Change::Revealed(coords(0, 2)),
+            ]
+        );
+        assert_eq!(game.state(), GameState::Lost);
+        assert!(game.reveal(coords(0, 0)).is_empty());
+        assert!(game.flag(coords(0, 0)).is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{ Deserialize, Serialize };
+use crate::{Coordinates, TileMap};
+use crate::errors::BoardError;
+
+/// When and how the bombs of the board are placed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum BoardGeneration {
+    /// Bombs are placed when the board is created
+    #[default]
+    Immediate,
+    /// Bombs are placed on the first uncovered tile, which is never a bomb.
+    /// With `safe_neighbors` its whole 3x3 square is kept clear so it opens an area
+    Deferred { safe_neighbors: bool },
+    /// Like `Deferred` with safe neighbors, but bombs are placed again until the
+    /// board can be cleared from the first tile without guessing.
+    /// After `attempts` failures the last board is kept
+    NoGuess { attempts: u32 },
+    /// Bombs are read from an `.mbf` board, which also sets the map size and bomb count.
+    /// Random bombs are placed when it can't be read
+    Fixed { path: PathBuf },
+}
+
+impl BoardGeneration {
+    /// Whether the bombs are only placed on the first uncover
+    pub fn is_deferred(&self) -> bool {
+        matches!(self, Self::Deferred { .. } | Self::NoGuess { .. })
+    }
+
+    /// Most bombs fitting on a map of the given size, wherever the first uncover lands:
+    /// the tiles kept clear around it, or a single tile, must stay free of bombs
+    pub fn max_bomb_count(&self, (width, height): (u16, u16)) -> u32 {
+        let safe_tiles = match self {
+            Self::Deferred { safe_neighbors: true } | Self::NoGuess { .. } => {
+                width.min(3) as u32 * height.min(3) as u32
+            }
+            _ => 1,
+        };
+        (width as u32 * height as u32).saturating_sub(safe_tiles)
+    }
+
+    /// Fails when the bombs can't fit, see `max_bomb_count`
+    pub(crate) fn check_bomb_count(&self, map_size: (u16, u16), bomb_count: u16) -> Result<(), BoardError> {
+        if bomb_count as u32 > self.max_bomb_count(map_size) {
+            return Err(BoardError::TooManyBombs {
+                bomb_count,
+                tile_count: map_size.0 as u32 * map_size.1 as u32,
+            });
+        }
+        Ok(())
+    }
+}
+
+
+/// Map of the given size with its bombs placed by `generation` from `seed`,
+/// `None` when they are only placed on the first uncover.
+/// A fixed board brings its own size and bomb count
+pub(crate) fn generate(
+    (width, height): (u16, u16),
+    bomb_count: u16,
+    generation: &BoardGeneration,
+    seed: u64,
+) -> Result<Option<TileMap>, BoardError> {
+    let mut tile_map = TileMap::empty(width, height);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    match generation {
+        BoardGeneration::Immediate => tile_map.set_bombs(bomb_count, &mut rng)?,
+        BoardGeneration::Deferred { .. } | BoardGeneration::NoGuess { .. } => return Ok(None),
+        BoardGeneration::Fixed { path } => match TileMap::load_mbf(path) {
+            Ok(fixed_map) => {
+                log::info!("Loaded board {}", path.display());
+                tile_map = fixed_map;
+            }
+            Err(e) => {
+                log::error!("{}, placing random bombs", e);
+                tile_map.set_bombs(bomb_count, &mut rng)?;
+            }
+        },
+    }
+    Ok(Some(tile_map))
+}
+
+/// Places the bombs of a deferred board around the first uncovered tile
+pub(crate) fn generate_around(
+    tile_map: &mut TileMap,
+    bomb_count: u16,
+    generation: &BoardGeneration,
+    start: Coordinates,
+    seed: u64,
+) -> Result<(), BoardError> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    match *generation {
+        BoardGeneration::Immediate | BoardGeneration::Fixed { .. } => (),
+        BoardGeneration::Deferred { safe_neighbors } => {
+            let safe_tiles = tile_map.safe_zone(start, safe_neighbors);
+            tile_map.set_bombs_around(bomb_count, &safe_tiles, &mut rng)?;
+        }
+        BoardGeneration::NoGuess { attempts } => {
+            match tile_map.set_bombs_no_guess(bomb_count, start, attempts, &mut rng) {
+                Err(e @ BoardError::NoGuessAttemptsExhausted { .. }) => {
+                    log::warn!("{}, the board may require guessing", e);
+                }
+                result => result?,
+            }
+        }
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(x: u16, y: u16) -> Coordinates {
+        Coordinates { x, y }
+    }
+
+    #[test]
+    fn first_uncover_keeps_its_square_clear() {
+        let generation = BoardGeneration::Deferred { safe_neighbors: true };
+        assert_eq!(generation.max_bomb_count((5, 5)), 16);
+        for seed in 0..20 {
+            let mut tile_map = TileMap::empty(5, 5);
+            generate_around(&mut tile_map, 16, &generation, coords(2, 2), seed).unwrap();
+            assert_eq!(tile_map.bomb_count(), 16);
+            for c in tile_map.safe_zone(coords(2, 2), true) {
+                assert!(!tile_map.is_bomb_at(c), "bomb at {} with seed {}", c, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn first_uncover_is_never_a_bomb() {
+        let generation = BoardGeneration::Deferred { safe_neighbors: false };
+        assert_eq!(generation.max_bomb_count((3, 3)), 8);
+        for seed in 0..20 {
+            let mut tile_map = TileMap::empty(3, 3);
+            generate_around(&mut tile_map, 8, &generation, coords(0, 0), seed).unwrap();
+            assert!(!tile_map.is_bomb_at(coords(0, 0)));
+        }
+    }
+    #[test]
+    fn same_seed_gives_the_same_board() {
+        let generate_with = |seed| generate((9, 9), 10, &BoardGeneration::Immediate, seed).unwrap().unwrap();
+        assert_eq!(generate_with(42).to_string(), generate_with(42).to_string());
+        assert_ne!(generate_with(42).to_string(), generate_with(43).to_string());
+
+        let generation = BoardGeneration::Deferred { safe_neighbors: true };
+        let generate_around_with = |seed| {
+            let mut tile_map = TileMap::empty(9, 9);
+            generate_around(&mut tile_map, 10, &generation, coords(4, 4), seed).unwrap();
+            tile_map.to_string()
+        };
+        assert_eq!(generate_around_with(7), generate_around_with(7));
+    }
+    #[test]
+    fn rejects_bombs_that_dont_fit() {
+        let generation = BoardGeneration::NoGuess { attempts: 10 };
+        assert_eq!(generation.max_bomb_count((3, 3)), 0);
+        assert_eq!(
+            generation.check_bomb_count((3, 3), 8),
+            Err(BoardError::TooManyBombs { bomb_count: 8, tile_count: 9 })
+        );
+        assert!(crate::Game::new((3, 3), 8, generation, 0).is_err());
+        assert_eq!(
+            generate((3, 3), 9, &BoardGeneration::Immediate, 0).unwrap_err(),
+            BoardError::TooManyBombs { bomb_count: 9, tile_count: 9 }
+        );
+    }
+}
\ No newline at end of file
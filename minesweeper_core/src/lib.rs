@@ -0,0 +1,20 @@
+pub mod errors;
+pub mod settings;
+pub mod solver;
+
+pub use coordinates::Coordinates;
+pub use difficulty::Difficulty;
+pub use game::{Change, Game, GameState};
+pub use generation::BoardGeneration;
+pub use settings::GameOptions;
+pub use tile::Tile;
+pub use tile_map::TileMap;
+
+mod coordinates;
+mod difficulty;
+mod game;
+mod generation;
+mod mbf;
+mod text;
+mod tile;
+mod tile_map;
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+use crate::Coordinates;
+use crate::errors::{BoardError, MbfError};
+use crate::Tile;
+use crate::TileMap;
+
+const HEADER_LENGTH: usize = 4;
+
+impl TileMap {
+    /// Reads a Minesweeper Arbiter `.mbf` board: the width and height on one byte each,
+    /// the mine count on two big endian bytes, then the column and row of every mine.
+    /// Rows are counted from the top, while the `TileMap` counts them from the bottom
+    pub fn from_mbf(bytes: &[u8]) -> Result<Self, MbfError> {
+        if bytes.len() < HEADER_LENGTH {
+            return Err(MbfError::Truncated {
+                length: bytes.len(),
+                expected: HEADER_LENGTH,
+            });
+        }
+        let (width, height) = (bytes[0] as u16, bytes[1] as u16);
+        if width == 0 || height == 0 {
+            return Err(MbfError::InvalidBoard(BoardError::EmptyMap { width, height }));
+        }
+        let mine_count = u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+        let expected = HEADER_LENGTH + mine_count * 2;
+        if bytes.len() < expected {
+            return Err(MbfError::Truncated {
+                length: bytes.len(),
+                expected,
+            });
+        }
+
+        let bombs: Vec<Coordinates> = bytes[HEADER_LENGTH..expected]
+            .chunks_exact(2)
+            .map(|mine| Coordinates {
+                x: mine[0] as u16,
+                // Rows beyond the map wrap around and are reported out of bounds
+                y: height.wrapping_sub(1).wrapping_sub(mine[1] as u16),
+            })
+            .collect();
+        let mut tile_map = Self::empty(width, height);
+        tile_map.set_bombs_at(&bombs).map_err(MbfError::InvalidBoard)?;
+        Ok(tile_map)
+    }
+
+    /// Writes the board in the `.mbf` format, fails beyond 255 columns or rows
+    pub fn to_mbf(&self) -> Result<Vec<u8>, MbfError> {
+        let too_large = |_| MbfError::TooLarge {
+            width: self.width(),
+            height: self.height(),
+        };
+        let width = u8::try_from(self.width()).map_err(too_large)?;
+        let height = u8::try_from(self.height()).map_err(too_large)?;
+
+        let mut bytes = vec![width, height];
+        bytes.extend(self.bomb_count().to_be_bytes());
+        for (row, line) in self.iter().rev().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                if *tile == Tile::Bomb {
+                    bytes.push(x as u8);
+                    bytes.push(row as u8);
+                }
+            }
+        }
+        Ok(bytes)
+    }
+
+    pub fn load_mbf(path: impl AsRef<Path>) -> Result<Self, MbfError> {
+        let bytes = fs::read(path).map_err(MbfError::Io)?;
+        Self::from_mbf(&bytes)
+    }
+
+    pub fn save_mbf(&self, path: impl AsRef<Path>) -> Result<(), MbfError> {
+        let bytes = self.to_mbf()?;
+        fs::write(path, bytes).map_err(MbfError::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let tile_map: TileMap = "*...\n....\n..**".parse().unwrap();
+        // Mines by column then row, rows counted from the top
+        let bytes = tile_map.to_mbf().unwrap();
+        assert_eq!(bytes, vec![4, 3, 0, 3, 0, 0, 2, 2, 3, 2]);
+        assert_eq!(TileMap::from_mbf(&bytes).unwrap().to_string(), tile_map.to_string());
+    }
+
+    #[test]
+    fn rejects_truncated_boards() {
+        assert!(matches!(
+            TileMap::from_mbf(&[3, 2]),
+            Err(MbfError::Truncated { length: 2, expected: 4 })
+        ));
+        assert!(matches!(
+            TileMap::from_mbf(&[3, 2, 0, 2, 0, 0]),
+            Err(MbfError::Truncated { length: 6, expected: 8 })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_boards() {
+        assert!(matches!(
+            TileMap::from_mbf(&[0, 0, 0, 0]),
+            Err(MbfError::InvalidBoard(BoardError::EmptyMap { width: 0, height: 0 }))
+        ));
+        assert!(matches!(
+            TileMap::from_mbf(&[2, 2, 0, 1, 5, 0]),
+            Err(MbfError::InvalidBoard(BoardError::BombOutOfBounds { .. }))
+        ));
+        assert!(matches!(
+            TileMap::empty(256, 1).to_mbf(),
+            Err(MbfError::TooLarge { width: 256, height: 1 })
+        ));
+    }
+}
\ No newline at end of file
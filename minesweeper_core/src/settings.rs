@@ -0,0 +1,83 @@
+use std::{fs, io};
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+use serde::{ Deserialize, Serialize };
+use crate::{BoardGeneration, Difficulty};
+use crate::errors::{BoardError, SettingsError};
+
+/// File storing the last used options, next to the working directory
+pub const SETTINGS_PATH: &str = "settings.ron";
+
+/// Options of a game, shared by every frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameOptions {
+    pub map_size: (u16, u16),
+    pub bomb_count: u16,
+    /// Uncovers a safe area when the board is created
+    pub safe_start: bool,
+    pub generation: BoardGeneration,
+    /// Seed of the bomb placement, a random one is picked when `None`
+    pub seed: Option<u64>,
+}
+
+/// Part of the settings every frontend understands, they store their own options next to it
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Settings {
+    game: GameOptions,
+}
+
+impl Default for GameOptions {
+    /// Intermediate board whose first uncover opens an area
+    fn default() -> Self {
+        let difficulty = Difficulty::Intermediate;
+        Self {
+            map_size: difficulty.map_size(),
+            bomb_count: difficulty.bomb_count(),
+            safe_start: false,
+            generation: BoardGeneration::Deferred { safe_neighbors: true },
+            seed: None,
+        }
+    }
+}
+
+impl GameOptions {
+    /// Checks that a board can be generated from the options
+    pub fn validate(&self) -> Result<(), BoardError> {
+        let (width, height) = self.map_size;
+        if width == 0 || height == 0 {
+            return Err(BoardError::EmptyMap { width, height });
+        }
+        self.generation.check_bomb_count(self.map_size, self.bomb_count)?;
+        if let BoardGeneration::NoGuess { attempts: 0 } = self.generation {
+            return Err(BoardError::NoGuessWithoutAttempts);
+        }
+        Ok(())
+    }
+}
+
+/// Reads the settings of a frontend, `None` if no settings were saved yet
+pub fn load_settings<T: DeserializeOwned>() -> Result<Option<T>, SettingsError> {
+    let content = match fs::read_to_string(SETTINGS_PATH) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(SettingsError::Io(e)),
+    };
+    ron::from_str(&content).map(Some).map_err(SettingsError::Parse)
+}
+
+pub fn save_settings<T: Serialize>(settings: &T) -> Result<(), SettingsError> {
+    let content = ron::ser::to_string_pretty(settings, PrettyConfig::default())
+        .map_err(SettingsError::Parse)?;
+    fs::write(SETTINGS_PATH, content).map_err(SettingsError::Io)
+}
+
+/// Reads the game options alone, ignoring the ones of the frontend that saved them
+pub fn load_options() -> Result<Option<GameOptions>, SettingsError> {
+    let options = load_settings::<Settings>()?.map(|settings| settings.game);
+    if let Some(options) = &options {
+        options.validate().map_err(SettingsError::Invalid)?;
+    }
+    Ok(options)
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use crate::Coordinates;
+use crate::Tile;
+use crate::TileMap;
+use crate::tile_map::SQUARE_COORDINATES;
+
+/// What the player knows about a tile
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KnownTile {
+    Covered,
+    /// Covered tile known to be a bomb, flags are trusted as such
+    Mine,
+    /// Covered tile known to be safe
+    Safe,
+    /// Uncovered tile with its neighbor bomb count
+    Revealed(u8),
+}
+
+/// Player view of a board, independent of any entity
+#[derive(Debug, Clone)]
+pub struct BoardView {
+    width: u16,
+    height: u16,
+    bomb_count: u16,
+    tiles: Vec<KnownTile>,
+}
+
+/// A revealed number: exactly `mines` bombs among the covered `cells`
+#[derive(Debug, Clone)]
+pub(crate) struct Constraint {
+    pub cells: Vec<Coordinates>,
+    pub mines: u8,
+}
+
+impl BoardView {
+    /// A fully covered board
+    pub fn covered(width: u16, height: u16, bomb_count: u16) -> Self {
+        Self {
+            width,
+            height,
+            bomb_count,
+            tiles: vec![KnownTile::Covered; width as usize * height as usize],
+        }
+    }
+
+    /// View of `tile_map` where only the `covered` tiles are hidden and the
+    /// `flagged` ones are considered bombs
+    pub fn from_tile_map(
+        tile_map: &TileMap,
+        covered: impl IntoIterator<Item = Coordinates>,
+        flagged: impl IntoIterator<Item = Coordinates>,
+    ) -> Self {
+        let mut view = Self::covered(tile_map.width(), tile_map.height(), tile_map.bomb_count());
+        for coords in view.coordinates().collect::<Vec<_>>() {
+            let tile = match tile_map[coords.y as usize][coords.x as usize] {
+                // An uncovered bomb is known
+                Tile::Bomb => KnownTile::Mine,
+                Tile::BombNeighbor(v) => KnownTile::Revealed(v),
+                Tile::Empty => KnownTile::Revealed(0),
+            };
+            view.set(coords, tile);
+        }
+        for coords in covered {
+            view.set(coords, KnownTile::Covered);
+        }
+        for coords in flagged {
+            if view.get(coords) == KnownTile::Covered {
+                view.set(coords, KnownTile::Mine);
+            }
+        }
+        view
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn bomb_count(&self) -> u16 {
+        self.bomb_count
+    }
+
+    pub fn get(&self, coordinates: Coordinates) -> KnownTile {
+        self.tiles[self.index(coordinates)]
+    }
+
+    pub fn set(&mut self, coordinates: Coordinates, tile: KnownTile) {
+        let index = self.index(coordinates);
+        self.tiles[index] = tile;
+    }
+
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinates> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coordinates { x, y }))
+    }
+
+    pub fn neighbors(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
+        let (width, height) = (self.width, self.height);
+        SQUARE_COORDINATES
+            .iter()
+            .map(move |tuple| coordinates + *tuple)
+            .filter(move |c| c.x < width && c.y < height)
+    }
+
+    /// Uncovers `coordinates` from `tile_map`, opening the neighbors of empty tiles
+    pub fn reveal(&mut self, tile_map: &TileMap, coordinates: Coordinates) {
+        let mut stack = vec![coordinates];
+        while let Some(coords) = stack.pop() {
+            if !matches!(self.get(coords), KnownTile::Covered | KnownTile::Safe) {
+                continue;
+            }
+            let count = match tile_map[coords.y as usize][coords.x as usize] {
+                Tile::Bomb => continue,
+                Tile::BombNeighbor(v) => v,
+                Tile::Empty => 0,
+            };
+            self.set(coords, KnownTile::Revealed(count));
+            if count == 0 {
+                stack.extend(self.neighbors(coords));
+            }
+        }
+    }
+
+    pub fn count(&self, tile: KnownTile) -> usize {
+        self.tiles.iter().filter(|t| **t == tile).count()
+    }
+
+    pub fn revealed_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|t| matches!(t, KnownTile::Revealed(_)))
+            .count()
+    }
+
+    /// Bombs not yet known as `Mine`
+    pub fn remaining_bombs(&self) -> usize {
+        (self.bomb_count as usize).saturating_sub(self.count(KnownTile::Mine))
+    }
+
+    fn index(&self, coordinates: Coordinates) -> usize {
+        coordinates.y as usize * self.width as usize + coordinates.x as usize
+    }
+
+    /// Constraints of every revealed tile still touching unknown covered tiles
+    pub(crate) fn constraints(&self) -> Vec<Constraint> {
+        let mut constraints = Vec::new();
+        for coords in self.coordinates() {
+            let count = match self.get(coords) {
+                KnownTile::Revealed(v) => v,
+                _ => continue,
+            };
+            let mut cells = Vec::new();
+            let mut mines = 0;
+            for neighbor in self.neighbors(coords) {
+                match self.get(neighbor) {
+                    KnownTile::Covered => cells.push(neighbor),
+                    KnownTile::Mine => mines += 1,
+                    KnownTile::Safe | KnownTile::Revealed(_) => (),
+                }
+            }
+            if !cells.is_empty() {
+                cells.sort();
+                constraints.push(Constraint {
+                    cells,
+                    mines: count.saturating_sub(mines),
+                });
+            }
+        }
+        constraints
+    }
+}
+
+impl Constraint {
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.cells.len() < other.cells.len()
+            && self.cells.iter().all(|c| other.cells.binary_search(c).is_ok())
+    }
+}
\ No newline at end of file
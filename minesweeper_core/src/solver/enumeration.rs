@@ -0,0 +1,209 @@
+use crate::Coordinates;
+use crate::solver::board_view::Constraint;
+use std::collections::HashMap;
+
+/// Components with more tiles are not enumerated, their tiles are treated
+/// like unconstrained ones
+pub const MAX_ENUMERATED_TILES: usize = 48;
+
+/// Frontier tiles linked together by shared constraints
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub cells: Vec<Coordinates>,
+    /// Local cell indices and bomb count of every constraint
+    constraints: Vec<(Vec<usize>, u8)>,
+}
+
+/// Every valid bomb layout of a component, grouped by bomb count.
+/// Counts are scaled down by a common factor to stay in `f64` range
+#[derive(Debug, Clone)]
+pub struct Enumeration {
+    /// Number of layouts with `k` bombs
+    pub solutions: Vec<f64>,
+    /// Number of layouts with `k` bombs where each cell is a bomb
+    pub cell_bombs: Vec<Vec<f64>>,
+}
+
+struct Search<'a> {
+    component: &'a Component,
+    cell_constraints: Vec<Vec<usize>>,
+    bombs_in: Vec<u8>,
+    unassigned_in: Vec<u8>,
+    layout: Vec<bool>,
+    bombs: usize,
+    max_bombs: usize,
+    result: Enumeration,
+}
+
+/// Splits the constraints into independent components
+pub fn components(constraints: &[Constraint]) -> Vec<Component> {
+    let mut index: HashMap<Coordinates, usize> = HashMap::default();
+    let mut cells = Vec::new();
+    for constraint in constraints {
+        for cell in constraint.cells.iter() {
+            index.entry(*cell).or_insert_with(|| {
+                cells.push(*cell);
+                cells.len() - 1
+            });
+        }
+    }
+
+    let mut roots: Vec<usize> = (0..cells.len()).collect();
+    for constraint in constraints {
+        let first = find(&mut roots, index[&constraint.cells[0]]);
+        for cell in constraint.cells.iter().skip(1) {
+            let other = find(&mut roots, index[cell]);
+            roots[other] = first;
+        }
+    }
+
+    let mut by_root: HashMap<usize, usize> = HashMap::default();
+    let mut components: Vec<Component> = Vec::new();
+    let mut local: HashMap<Coordinates, usize> = HashMap::default();
+    for (i, cell) in cells.iter().enumerate() {
+        let root = find(&mut roots, i);
+        let component = *by_root.entry(root).or_insert_with(|| {
+            components.push(Component {
+                cells: Vec::new(),
+                constraints: Vec::new(),
+            });
+            components.len() - 1
+        });
+        local.insert(*cell, components[component].cells.len());
+        components[component].cells.push(*cell);
+    }
+    for constraint in constraints {
+        let root = find(&mut roots, index[&constraint.cells[0]]);
+        let component = &mut components[by_root[&root]];
+        let cells = constraint.cells.iter().map(|c| local[c]).collect();
+        component.constraints.push((cells, constraint.mines));
+    }
+    components
+}
+
+/// Union-find root of `i`
+fn find(roots: &mut [usize], i: usize) -> usize {
+    let mut i = i;
+    while roots[i] != i {
+        roots[i] = roots[roots[i]];
+        i = roots[i];
+    }
+    i
+}
+
+impl Component {
+    /// Lists every bomb layout satisfying the constraints, with at most `max_bombs` bombs
+    pub fn enumerate(&self, max_bombs: usize) -> Enumeration {
+        let mut cell_constraints = vec![Vec::new(); self.cells.len()];
+        for (i, (cells, _)) in self.constraints.iter().enumerate() {
+            for cell in cells {
+                cell_constraints[*cell].push(i);
+            }
+        }
+        let max_bombs = max_bombs.min(self.cells.len());
+        let mut search = Search {
+            component: self,
+            cell_constraints,
+            bombs_in: vec![0; self.constraints.len()],
+            unassigned_in: self.constraints.iter().map(|(c, _)| c.len() as u8).collect(),
+            layout: vec![false; self.cells.len()],
+            bombs: 0,
+            max_bombs,
+            result: Enumeration {
+                solutions: vec![0.0; max_bombs + 1],
+                cell_bombs: vec![vec![0.0; self.cells.len()]; max_bombs + 1],
+            },
+        };
+        search.run(0);
+
+        let mut result = search.result;
+        let scale = result.solutions.iter().copied().fold(0.0, f64::max);
+        if scale > 0.0 {
+            for (solutions, cells) in result.solutions.iter_mut().zip(result.cell_bombs.iter_mut()) {
+                *solutions /= scale;
+                cells.iter_mut().for_each(|c| *c /= scale);
+            }
+        }
+        result
+    }
+}
+
+impl<'a> Search<'a> {
+    fn run(&mut self, cell: usize) {
+        if cell == self.layout.len() {
+            self.result.solutions[self.bombs] += 1.0;
+            let cell_bombs = &mut self.result.cell_bombs[self.bombs];
+            for (i, bomb) in self.layout.iter().enumerate() {
+                if *bomb {
+                    cell_bombs[i] += 1.0;
+                }
+            }
+            return;
+        }
+
+        for bomb in [false, true] {
+            if bomb && self.bombs == self.max_bombs {
+                continue;
+            }
+            if self.assign(cell, bomb) {
+                self.run(cell + 1);
+            }
+            self.unassign(cell, bomb);
+        }
+    }
+
+    /// Assigns `cell`, returning whether every touched constraint can still be met
+    fn assign(&mut self, cell: usize, bomb: bool) -> bool {
+        self.layout[cell] = bomb;
+        if bomb {
+            self.bombs += 1;
+        }
+        let mut valid = true;
+        for constraint in self.cell_constraints[cell].iter().copied() {
+            self.unassigned_in[constraint] -= 1;
+            if bomb {
+                self.bombs_in[constraint] += 1;
+            }
+            let expected = self.component.constraints[constraint].1;
+            let bombs = self.bombs_in[constraint];
+            if bombs > expected || bombs + self.unassigned_in[constraint] < expected {
+                valid = false;
+            }
+        }
+        valid
+    }
+
+    fn unassign(&mut self, cell: usize, bomb: bool) {
+        self.layout[cell] = false;
+        if bomb {
+            self.bombs -= 1;
+        }
+        for constraint in self.cell_constraints[cell].iter().copied() {
+            self.unassigned_in[constraint] += 1;
+            if bomb {
+                self.bombs_in[constraint] -= 1;
+            }
+        }
+    }
+}
+
+/// Distribution of the total bomb count of independent enumerations
+pub fn convolve<'a>(enumerations: impl Iterator<Item = &'a Enumeration>) -> Vec<f64> {
+    let mut total = vec![1.0];
+    for enumeration in enumerations {
+        let mut next = vec![0.0; total.len() + enumeration.solutions.len() - 1];
+        for (t, a) in total.iter().enumerate() {
+            for (k, b) in enumeration.solutions.iter().enumerate() {
+                next[t + k] += a * b;
+            }
+        }
+        total = next;
+    }
+    total
+}
+
+/// Natural logarithm of the binomial coefficient `n` choose `k`
+pub fn ln_binomial(n: usize, k: usize) -> f64 {
+    let k = k.min(n - k);
+    (0..k).map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln()).sum()
+}
\ No newline at end of file
@@ -0,0 +1,367 @@
+pub use board_view::{BoardView, KnownTile};
+
+mod board_view;
+mod enumeration;
+
+use crate::Coordinates;
+use crate::TileMap;
+use std::collections::HashMap;
+use enumeration::{Enumeration, MAX_ENUMERATED_TILES};
+
+/// Tiles proven safe or mined by logical deductions
+#[derive(Debug, Clone, Default)]
+pub struct Deductions {
+    pub safe: Vec<Coordinates>,
+    pub mines: Vec<Coordinates>,
+}
+
+/// Everything the solver knows about the covered tiles of a view
+#[derive(Debug, Clone, Default)]
+pub struct Solution {
+    /// Covered tiles that can't be bombs
+    pub safe: Vec<Coordinates>,
+    /// Covered tiles that must be bombs, flags excluded
+    pub mines: Vec<Coordinates>,
+    /// Bomb probability of every covered tile, flags excluded
+    pub probabilities: HashMap<Coordinates, f64>,
+}
+
+impl Deductions {
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.mines.is_empty()
+    }
+
+    fn dedup(mut self) -> Self {
+        self.safe.sort();
+        self.safe.dedup();
+        self.mines.sort();
+        self.mines.dedup();
+        self
+    }
+}
+
+impl Solution {
+    /// Covered tile least likely to be a bomb
+    pub fn safest_tile(&self) -> Option<(Coordinates, f64)> {
+        self.probabilities
+            .iter()
+            .map(|(c, p)| (*c, *p))
+            .min_by(|(ca, pa), (cb, pb)| pa.total_cmp(pb).then(ca.cmp(cb)))
+    }
+}
+
+/// Finds the covered tiles that are certainly safe or mined, using single tile
+/// constraints, constraint subsets and the global bomb count
+pub fn deduce(view: &BoardView) -> Deductions {
+    let mut deductions = Deductions::default();
+    let constraints = view.constraints();
+
+    for constraint in constraints.iter() {
+        if constraint.mines == 0 {
+            deductions.safe.extend(constraint.cells.iter().copied());
+        } else if constraint.mines as usize == constraint.cells.len() {
+            deductions.mines.extend(constraint.cells.iter().copied());
+        }
+    }
+
+    for small in constraints.iter() {
+        for big in constraints.iter().filter(|big| small.is_subset_of(big)) {
+            let rest = big.cells.iter().filter(|c| small.cells.binary_search(c).is_err());
+            let mines = big.mines.saturating_sub(small.mines) as usize;
+            if mines == 0 {
+                deductions.safe.extend(rest);
+            } else if mines == big.cells.len() - small.cells.len() {
+                deductions.mines.extend(rest);
+            }
+        }
+    }
+
+    if deductions.is_empty() {
+        let covered = view.count(KnownTile::Covered);
+        let remaining = view.remaining_bombs();
+        let covered_tiles = view.coordinates().filter(|c| view.get(*c) == KnownTile::Covered);
+        if remaining == 0 {
+            deductions.safe.extend(covered_tiles);
+        } else if remaining == covered {
+            deductions.mines.extend(covered_tiles);
+        }
+    }
+
+    deductions.dedup()
+}
+
+/// Solves `view`: logical deductions first, then an exact enumeration of the bomb
+/// layouts of every frontier component, weighted by the ways to place the
+/// remaining bombs on the other covered tiles.
+///
+/// Components larger than `MAX_ENUMERATED_TILES` are treated as unconstrained tiles,
+/// making their probabilities approximate. Flags are trusted to be bombs.
+pub fn solve(view: &BoardView) -> Solution {
+    let mut view = view.clone();
+    let mut solution = Solution::default();
+
+    loop {
+        let deductions = deduce(&view);
+        if deductions.is_empty() {
+            break;
+        }
+        for coords in deductions.safe {
+            view.set(coords, KnownTile::Safe);
+            solution.safe.push(coords);
+            solution.probabilities.insert(coords, 0.0);
+        }
+        for coords in deductions.mines {
+            view.set(coords, KnownTile::Mine);
+            solution.mines.push(coords);
+            solution.probabilities.insert(coords, 1.0);
+        }
+    }
+
+    let remaining = view.remaining_bombs();
+    let components: Vec<_> = enumeration::components(&view.constraints())
+        .into_iter()
+        .filter(|c| c.cells.len() <= MAX_ENUMERATED_TILES)
+        .collect();
+    let enumerations: Vec<Enumeration> = components.iter().map(|c| c.enumerate(remaining)).collect();
+    let frontier: Vec<Coordinates> = components.iter().flat_map(|c| c.cells.iter().copied()).collect();
+    let interior: Vec<Coordinates> = view
+        .coordinates()
+        .filter(|c| view.get(*c) == KnownTile::Covered && !frontier.contains(c))
+        .collect();
+
+    // Relative weight of having `t` bombs on the frontier
+    let feasible = |t: usize| t <= remaining && remaining - t <= interior.len();
+    let max_frontier_bombs: usize = enumerations.iter().map(|e| e.solutions.len() - 1).sum();
+    let max_ln = (0..=max_frontier_bombs)
+        .filter(|t| feasible(*t))
+        .map(|t| enumeration::ln_binomial(interior.len(), remaining - t))
+        .fold(f64::NEG_INFINITY, f64::max);
+    let weight = |t: usize| match feasible(t) {
+        true => (enumeration::ln_binomial(interior.len(), remaining - t) - max_ln).exp(),
+        false => 0.0,
+    };
+
+    let distribution = enumeration::convolve(enumerations.iter());
+    let total: f64 = distribution.iter().enumerate().map(|(t, d)| d * weight(t)).sum();
+    if total <= 0.0 {
+        // Inconsistent view, most likely a misplaced flag
+        let covered = view.count(KnownTile::Covered).max(1);
+        let density = remaining as f64 / covered as f64;
+        for coords in view.coordinates().filter(|c| view.get(*c) == KnownTile::Covered) {
+            solution.probabilities.insert(coords, density.min(1.0));
+        }
+        return solution;
+    }
+
+    for (i, (component, enumeration)) in components.iter().zip(enumerations.iter()).enumerate() {
+        let others = enumeration::convolve(
+            enumerations.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, e)| e),
+        );
+        for (cell, coords) in component.cells.iter().enumerate() {
+            let mut bomb_weight = 0.0;
+            let (mut can_be_bomb, mut can_be_safe) = (false, false);
+            for (k, solutions) in enumeration.solutions.iter().enumerate() {
+                let bombs = enumeration.cell_bombs[k][cell];
+                for (t, other) in others.iter().enumerate() {
+                    if *other <= 0.0 || *solutions <= 0.0 || !feasible(k + t) {
+                        continue;
+                    }
+                    bomb_weight += bombs * other * weight(k + t);
+                    can_be_bomb |= bombs > 0.0;
+                    can_be_safe |= bombs < *solutions;
+                }
+            }
+            solution.probabilities.insert(*coords, bomb_weight / total);
+            if !can_be_bomb {
+                solution.safe.push(*coords);
+            } else if !can_be_safe {
+                solution.mines.push(*coords);
+            }
+        }
+    }
+
+    if !interior.is_empty() {
+        let mut bomb_weight = 0.0;
+        let (mut can_be_bomb, mut can_be_safe) = (false, false);
+        for (t, d) in distribution.iter().enumerate() {
+            if *d <= 0.0 || !feasible(t) {
+                continue;
+            }
+            let bombs = remaining - t;
+            bomb_weight += d * weight(t) * bombs as f64 / interior.len() as f64;
+            can_be_bomb |= bombs > 0;
+            can_be_safe |= bombs < interior.len();
+        }
+        for coords in interior {
+            solution.probabilities.insert(coords, bomb_weight / total);
+            if !can_be_bomb {
+                solution.safe.push(coords);
+            } else if !can_be_safe {
+                solution.mines.push(coords);
+            }
+        }
+    }
+
+    solution.safe.sort();
+    solution.mines.sort();
+    solution
+}
+
+/// Whether `tile_map` can be cleared from `start` without ever guessing
+pub fn solves_without_guessing(tile_map: &TileMap, start: Coordinates) -> bool {
+    let mut view = BoardView::covered(tile_map.width(), tile_map.height(), tile_map.bomb_count());
+    let safe_count = tile_map.tile_count() as usize - tile_map.bomb_count() as usize;
+    view.reveal(tile_map, start);
+
+    loop {
+        if view.revealed_count() == safe_count {
+            return true;
+        }
+
+        let deductions = deduce(&view);
+        if deductions.is_empty() {
+            return false;
+        }
+        for coords in deductions.mines {
+            view.set(coords, KnownTile::Mine);
+        }
+        for coords in deductions.safe {
+            view.reveal(tile_map, coords);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(x: u16, y: u16) -> Coordinates {
+        Coordinates { x, y }
+    }
+
+    /// View written from the top row: `#` is covered, `F` flagged and digits are revealed
+    fn view(rows: &[&str], bomb_count: u16) -> BoardView {
+        let height = rows.len() as u16;
+        let width = rows[0].len() as u16;
+        let mut view = BoardView::covered(width, height, bomb_count);
+        for (row, line) in rows.iter().enumerate() {
+            for (x, character) in line.chars().enumerate() {
+                let tile = match character {
+                    '#' => KnownTile::Covered,
+                    'F' => KnownTile::Mine,
+                    _ => KnownTile::Revealed(character.to_digit(10).unwrap() as u8),
+                };
+                view.set(coords(x as u16, height - 1 - row as u16), tile);
+            }
+        }
+        view
+    }
+
+    /// Bomb probability of every covered tile over all the layouts matching the view
+    fn brute_force(view: &BoardView) -> HashMap<Coordinates, f64> {
+        let covered: Vec<Coordinates> = view
+            .coordinates()
+            .filter(|c| view.get(*c) == KnownTile::Covered)
+            .collect();
+        let mut bombs = vec![0u32; covered.len()];
+        let mut layouts = 0;
+        for mask in 0u32..1 << covered.len() {
+            if mask.count_ones() as usize != view.remaining_bombs() {
+                continue;
+            }
+            let is_bomb = |c: &Coordinates| {
+                view.get(*c) == KnownTile::Mine
+                    || covered.iter().position(|o| o == c).is_some_and(|i| mask & 1 << i != 0)
+            };
+            let consistent = view.coordinates().all(|c| match view.get(c) {
+                KnownTile::Revealed(v) => view.neighbors(c).filter(is_bomb).count() == v as usize,
+                _ => true,
+            });
+            if consistent {
+                layouts += 1;
+                for (i, count) in bombs.iter_mut().enumerate() {
+                    *count += mask >> i & 1;
+                }
+            }
+        }
+        covered
+            .into_iter()
+            .zip(bombs)
+            .map(|(c, count)| (c, count as f64 / layouts as f64))
+            .collect()
+    }
+
+    fn assert_matches_brute_force(view: &BoardView) {
+        let solution = solve(view);
+        for (coords, probability) in brute_force(view) {
+            let solved = solution.probabilities[&coords];
+            assert!(
+                (solved - probability).abs() < 1e-9,
+                "{} has a probability of {} instead of {}",
+                coords, solved, probability
+            );
+            assert_eq!(solution.safe.contains(&coords), probability == 0.0);
+            assert_eq!(solution.mines.contains(&coords), probability == 1.0);
+        }
+    }
+
+    #[test]
+    fn single_tile_finds_mines() {
+        let deductions = deduce(&view(&["1#", "11"], 1));
+        assert_eq!(deductions.mines, vec![coords(1, 1)]);
+        assert!(deductions.safe.is_empty());
+    }
+
+    #[test]
+    fn single_tile_finds_safe_tiles() {
+        let deductions = deduce(&view(&["F#", "1#"], 1));
+        assert_eq!(deductions.safe, vec![coords(1, 0), coords(1, 1)]);
+        assert!(deductions.mines.is_empty());
+    }
+
+    #[test]
+    fn subset_finds_mines() {
+        let view = view(&["###", "121"], 2);
+        let deductions = deduce(&view);
+        assert_eq!(deductions.mines, vec![coords(0, 1), coords(2, 1)]);
+        assert!(deductions.safe.is_empty());
+        assert_eq!(solve(&view).safe, vec![coords(1, 1)]);
+    }
+
+    #[test]
+    fn bomb_count_settles_unconstrained_tiles() {
+        let deductions = deduce(&view(&["1F##"], 1));
+        assert_eq!(deductions.safe, vec![coords(2, 0), coords(3, 0)]);
+        let deductions = deduce(&view(&["1F##"], 3));
+        assert_eq!(deductions.mines, vec![coords(2, 0), coords(3, 0)]);
+        let deductions = deduce(&view(&["1F##"], 2));
+        assert!(deductions.is_empty());
+    }
+
+    #[test]
+    fn enumeration_matches_brute_force() {
+        assert_matches_brute_force(&view(&["#######", "#1###2#", "#######"], 4));
+        assert_matches_brute_force(&view(&["####", "#21#", "####"], 3));
+
+        let tile_map: TileMap = "*....\n.....\n..*..\n*...*".parse().unwrap();
+        let mut view = BoardView::covered(tile_map.width(), tile_map.height(), tile_map.bomb_count());
+        view.reveal(&tile_map, coords(4, 3));
+        assert_matches_brute_force(&view);
+    }
+
+    #[test]
+    fn solves_boards_without_guessing() {
+        let tile_map: TileMap = "*..\n...\n...".parse().unwrap();
+        assert!(solves_without_guessing(&tile_map, coords(2, 0)));
+        // The mine is found next to the opening, the last tile by the bomb count
+        let tile_map: TileMap = ".*..".parse().unwrap();
+        assert!(solves_without_guessing(&tile_map, coords(3, 0)));
+    }
+
+    #[test]
+    fn detects_guesses() {
+        // The last mine is on either of the two top tiles
+        let tile_map: TileMap = "*.\n..\n..".parse().unwrap();
+        assert!(!solves_without_guessing(&tile_map, coords(0, 0)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,144 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use crate::Coordinates;
+use crate::errors::TileMapParseError;
+use crate::TileMap;
+
+const MINE: char = '*';
+const SAFE: char = '.';
+
+/// Plain text board, for hand written boards and test fixtures:
+///
+/// ```text
+/// 4x3 2
+/// *...
+/// ....
+/// ...*
+/// ```
+///
+/// The optional header gives the width, height and mine count, checked against the grid.
+/// Each following line is a row, from the top, with `*` for a mine and `.` for a safe tile.
+/// Surrounding whitespace and blank lines are ignored
+impl FromStr for TileMap {
+    type Err = TileMapParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().map(str::trim).filter(|line| !line.is_empty()).peekable();
+        let header = match lines.peek() {
+            Some(line) if line.starts_with(|c: char| c.is_ascii_digit()) => {
+                let header = parse_header(line)
+                    .ok_or_else(|| TileMapParseError::InvalidHeader(line.to_string()))?;
+                lines.next();
+                Some(header)
+            }
+            _ => None,
+        };
+        let rows: Vec<&str> = lines.collect();
+
+        let width = rows.first().ok_or(TileMapParseError::Empty)?.chars().count();
+        let height = rows.len();
+        let too_large = |_| TileMapParseError::TooLarge { width, height };
+        let (width, height) = (
+            u16::try_from(width).map_err(too_large)?,
+            u16::try_from(height).map_err(too_large)?,
+        );
+        let mut bombs = Vec::new();
+        for (row, line) in rows.iter().enumerate() {
+            let length = line.chars().count();
+            if length != width as usize {
+                return Err(TileMapParseError::UnevenRow { row, length, expected: width as usize });
+            }
+            let y = height - 1 - row as u16;
+            for (x, character) in line.chars().enumerate() {
+                match character {
+                    MINE => bombs.push(Coordinates { x: x as u16, y }),
+                    SAFE => (),
+                    _ => return Err(TileMapParseError::InvalidTile { row, column: x, character }),
+                }
+            }
+        }
+
+        if let Some(expected) = header {
+            let found = (width, height, bombs.len());
+            if expected != found {
+                return Err(TileMapParseError::HeaderMismatch { expected, found });
+            }
+        }
+        let mut tile_map = Self::empty(width, height);
+        tile_map.set_bombs_at(&bombs).map_err(TileMapParseError::InvalidBoard)?;
+        Ok(tile_map)
+    }
+}
+
+/// Reads a `<width>x<height> <mines>` header
+fn parse_header(line: &str) -> Option<(u16, u16, usize)> {
+    let (size, mines) = line.split_once(char::is_whitespace)?;
+    let (width, height) = size.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?, mines.trim().parse().ok()?))
+}
+
+/// Writes the board in the format read by `from_str`, header included
+impl Display for TileMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}x{} {}", self.width(), self.height(), self.bomb_count())?;
+        for line in self.iter().rev() {
+            let row: String = line
+                .iter()
+                .map(|tile| if tile.is_bomb() { MINE } else { SAFE })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let text = "4x3 2\n*...\n....\n...*\n";
+        let tile_map: TileMap = text.parse().unwrap();
+        assert_eq!((tile_map.width(), tile_map.height(), tile_map.bomb_count()), (4, 3, 2));
+        assert!(tile_map.is_bomb_at(Coordinates { x: 0, y: 2 }));
+        assert!(tile_map.is_bomb_at(Coordinates { x: 3, y: 0 }));
+        assert_eq!(tile_map.to_string(), text);
+    }
+
+    #[test]
+    fn header_is_optional() {
+        let tile_map: TileMap = "\n  *..\n  ...\n\n".parse().unwrap();
+        assert_eq!(tile_map.to_string(), "3x2 1\n*..\n...\n");
+    }
+
+    #[test]
+    fn rejects_header_mismatch() {
+        assert_eq!(
+            "3x3 2\n*...\n....\n...*".parse::<TileMap>().unwrap_err(),
+            TileMapParseError::HeaderMismatch { expected: (3, 3, 2), found: (4, 3, 2) }
+        );
+        assert_eq!(
+            "4x 2\n*...".parse::<TileMap>().unwrap_err(),
+            TileMapParseError::InvalidHeader("4x 2".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_uneven_rows() {
+        assert_eq!(
+            "*...\n..\n...*".parse::<TileMap>().unwrap_err(),
+            TileMapParseError::UnevenRow { row: 1, length: 2, expected: 4 }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_tiles() {
+        assert_eq!(
+            "*..\n.x.".parse::<TileMap>().unwrap_err(),
+            TileMapParseError::InvalidTile { row: 1, column: 1, character: 'x' }
+        );
+        assert_eq!("".parse::<TileMap>().unwrap_err(), TileMapParseError::Empty);
+    }
+}
\ No newline at end of file
@@ -1,7 +1,8 @@
 #[cfg(feature = "debug")]
 use colored::Colorize;
+use serde::{ Deserialize, Serialize };
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Tile {
     Bomb,
     BombNeighbor(u8),
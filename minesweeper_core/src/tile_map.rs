@@ -0,0 +1,390 @@
+use crate::{Coordinates, Tile, errors::BoardError, solver};
+use std::ops::{ Deref, DerefMut };
+use rand::Rng;
+use serde::{ Deserialize, Serialize };
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TileMap {
+    bomb_count: u16,
+    height: u16,
+    width: u16,
+    map: Vec<Vec<Tile>>,
+}
+
+pub(crate) const SQUARE_COORDINATES: [(i8, i8); 8] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top Left
+    (-1, 1),
+    // Top
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+impl TileMap {
+    pub fn empty(width: u16, height: u16) -> Self {
+        let map = (0..height)
+            .map(|_| (0..width).map(|_| Tile::Empty).collect())
+            .collect();
+
+        Self { 
+            bomb_count: 0,
+            height,
+            width,
+            map
+        }
+    }
+
+    pub fn safe_quare_at(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
+        SQUARE_COORDINATES
+            .iter()
+            .copied()
+            .map(move |tuple| coordinates + tuple)
+    }
+
+    pub fn in_bounds(&self, coordinates: Coordinates) -> bool {
+        coordinates.x < self.width && coordinates.y < self.height
+    }
+
+    /// Tiles that must stay free of bombs when generating around `coordinates`
+    pub fn safe_zone(&self, coordinates: Coordinates, with_neighbors: bool) -> Vec<Coordinates> {
+        let mut zone = vec![coordinates];
+        if with_neighbors {
+            zone.extend(self.safe_quare_at(coordinates));
+        }
+        zone.retain(|c| self.in_bounds(*c));
+        zone
+    }
+
+    pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
+        if coordinates.x >= self.width || coordinates.y >= self.height {
+            return false;
+        }
+        self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
+    }
+
+    pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
+        if self.is_bomb_at(coordinates) {
+            return 0;
+        }
+
+        let res = self
+            .safe_quare_at(coordinates)
+            .filter(|coord| self.is_bomb_at(*coord))
+            .count();
+
+        res as u8
+    }
+
+    pub fn tile_count(&self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    /// Maximum amount of bombs leaving at least one safe tile
+    pub fn max_bombs(&self) -> u16 {
+        self.max_bombs_around(&[])
+    }
+
+    /// Maximum amount of bombs fitting outside of `safe_tiles`, leaving at least one safe tile
+    pub fn max_bombs_around(&self, safe_tiles: &[Coordinates]) -> u16 {
+        let safe_count = safe_tiles.len().max(1) as u32;
+        self.tile_count().saturating_sub(safe_count).min(u16::MAX as u32) as u16
+    }
+
+    pub fn set_bombs(&mut self, bomb_count: u16, rng: &mut impl Rng) -> Result<(), BoardError> {
+        self.set_bombs_around(bomb_count, &[], rng)
+    }
+
+    /// Places bombs until the map can be cleared from `start` without guessing,
+    /// the last attempt is kept when every one of them fails. At least one attempt is made
+    pub fn set_bombs_no_guess(
+        &mut self,
+        bomb_count: u16,
+        start: Coordinates,
+        attempts: u32,
+        rng: &mut impl Rng,
+    ) -> Result<(), BoardError> {
+        let safe_tiles = self.safe_zone(start, true);
+        let attempts = attempts.max(1);
+        for _ in 0..attempts {
+            self.clear();
+            self.set_bombs_around(bomb_count, &safe_tiles, rng)?;
+            if solver::solves_without_guessing(self, start) {
+                return Ok(());
+            }
+        }
+        Err(BoardError::NoGuessAttemptsExhausted { attempts })
+    }
+
+    /// Removes every bomb from the map
+    pub fn clear(&mut self) {
+        self.bomb_count = 0;
+        for line in self.map.iter_mut() {
+            line.fill(Tile::Empty);
+        }
+    }
+
+    /// Randomly places `bomb_count` bombs, never on any of the `safe_tiles`
+    pub fn set_bombs_around(
+        &mut self,
+        bomb_count: u16,
+        safe_tiles: &[Coordinates],
+        rng: &mut impl Rng,
+    ) -> Result<(), BoardError> {
+        if bomb_count > self.max_bombs_around(safe_tiles) {
+            return Err(BoardError::TooManyBombs {
+                bomb_count,
+                tile_count: self.tile_count(),
+            });
+        }
+        self.bomb_count = bomb_count;
+        let mut remaining_bombs = bomb_count;
+
+        while remaining_bombs > 0 {
+            let (x, y) = (
+                rng.gen_range(0..self.width) as usize,
+                rng.gen_range(0..self.height) as usize,
+            );
+
+            let coords = Coordinates { x: x as u16, y: y as u16 };
+            if safe_tiles.contains(&coords) {
+                continue;
+            }
+
+            if let Tile::Empty = self[y][x] {
+                self[y][x] = Tile::Bomb;
+                remaining_bombs -= 1;
+            }
+        }
+        self.set_bomb_neighbors();
+        Ok(())
+    }
+
+    /// Places bombs on exactly the given tiles, duplicates are only counted once
+    pub fn set_bombs_at(&mut self, bombs: &[Coordinates]) -> Result<(), BoardError> {
+        if let Some(coordinates) = bombs.iter().find(|c| !self.in_bounds(**c)) {
+            return Err(BoardError::BombOutOfBounds { coordinates: *coordinates });
+        }
+        self.clear();
+        for coords in bombs {
+            self[coords.y as usize][coords.x as usize] = Tile::Bomb;
+        }
+        let bomb_count = self.iter().flatten().filter(|tile| tile.is_bomb()).count();
+        if bomb_count > self.max_bombs() as usize {
+            self.clear();
+            return Err(BoardError::TooManyBombs {
+                bomb_count: bomb_count.min(u16::MAX as usize) as u16,
+                tile_count: self.tile_count(),
+            });
+        }
+        self.bomb_count = bomb_count as u16;
+        self.set_bomb_neighbors();
+        Ok(())
+    }
+
+    /// Sets the neighbor bomb count of every tile around the bombs
+    fn set_bomb_neighbors(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let coords = Coordinates{ x, y };
+
+                if self.is_bomb_at(coords) {
+                    continue;
+                }
+
+                let num = self.bomb_count_at(coords);
+                if num == 0 {
+                    continue;
+                }
+
+                let tile = &mut self[y as usize][x as usize];
+                *tile = Tile::BombNeighbor(num);
+            }
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn console_output(&self) -> String {
+        let mut buffer = format!(
+            "Map ({}, {}) with {} bombs: \n",
+            self.width, self.height, self.bomb_count
+        );
+
+        let line: String = (0..=(self.width + 1)).into_iter().map(|_| '-').collect();
+        buffer = format!("{}{}\n", buffer, line);
+
+        for line in self.iter().rev() {
+            buffer = format!("{}|", buffer);
+            for tile in line.iter() {
+                buffer = format!("{}{}", buffer, tile.console_output());
+            }
+            buffer = format!("{}|\n", buffer);
+        }
+
+        format!("{}{}", buffer, line)
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn bomb_count(&self) -> u16 {
+        self.bomb_count
+    }
+
+    /// Bechtel's Board Benchmark Value: the minimum amount of left clicks clearing
+    /// the map, one per opening plus one per number not bordering an opening
+    pub fn bbbv(&self) -> u32 {
+        self.bbbv_clicks().len() as u32
+    }
+
+    /// Part of the 3BV already cleared, an opening counts as soon as one of its
+    /// empty tiles is uncovered
+    pub fn solved_bbbv(&self, is_uncovered: impl Fn(Coordinates) -> bool) -> u32 {
+        self.bbbv_clicks()
+            .iter()
+            .filter(|click| click.iter().any(|c| is_uncovered(*c)))
+            .count() as u32
+    }
+
+    /// Amount of openings, areas of empty tiles uncovered by a single click
+    pub fn openings(&self) -> u32 {
+        self.bbbv_clicks()
+            .iter()
+            .filter(|click| self.map[click[0].y as usize][click[0].x as usize] == Tile::Empty)
+            .count() as u32
+    }
+
+    /// Tiles of every click counted in the 3BV: the empty tiles of each opening,
+    /// then each isolated number alone
+    fn bbbv_clicks(&self) -> Vec<Vec<Coordinates>> {
+        let mut clicks = Vec::new();
+        let mut visited = vec![vec![false; self.width as usize]; self.height as usize];
+        let coordinates = (0..self.height).flat_map(|y| (0..self.width).map(move |x| Coordinates { x, y }));
+
+        for start in coordinates.clone() {
+            if visited[start.y as usize][start.x as usize]
+                || self.map[start.y as usize][start.x as usize] != Tile::Empty
+            {
+                continue;
+            }
+            let mut opening = Vec::new();
+            let mut stack = vec![start];
+            visited[start.y as usize][start.x as usize] = true;
+            while let Some(coords) = stack.pop() {
+                opening.push(coords);
+                for neighbor in self.safe_quare_at(coords).filter(|c| self.in_bounds(*c)) {
+                    let seen = &mut visited[neighbor.y as usize][neighbor.x as usize];
+                    if *seen || self.map[neighbor.y as usize][neighbor.x as usize].is_bomb() {
+                        continue;
+                    }
+                    *seen = true;
+                    // Numbers around the opening are uncovered with it
+                    if self.map[neighbor.y as usize][neighbor.x as usize] == Tile::Empty {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            clicks.push(opening);
+        }
+
+        for coords in coordinates {
+            if !visited[coords.y as usize][coords.x as usize]
+                && matches!(self.map[coords.y as usize][coords.x as usize], Tile::BombNeighbor(_))
+            {
+                clicks.push(vec![coords]);
+            }
+        }
+        clicks
+    }
+
+    /// Whether the dimensions, bomb count and neighbor counts all agree,
+    /// which only deserialized maps can break
+    pub fn is_consistent(&self) -> bool {
+        if self.map.len() != self.height as usize
+            || self.map.iter().any(|line| line.len() != self.width as usize)
+        {
+            return false;
+        }
+        let bombs = self.map.iter().flatten().filter(|tile| tile.is_bomb()).count();
+        bombs == self.bomb_count as usize
+            && (0..self.height).all(|y| {
+                (0..self.width).all(|x| {
+                    let coordinates = Coordinates { x, y };
+                    match self.map[y as usize][x as usize] {
+                        Tile::Bomb => true,
+                        Tile::BombNeighbor(v) => v == self.bomb_count_at(coordinates),
+                        Tile::Empty => self.bomb_count_at(coordinates) == 0,
+                    }
+                })
+            })
+    }
+}
+
+impl Deref for TileMap {
+    type Target = Vec<Vec<Tile>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl DerefMut for TileMap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn bbbv_counts_openings_and_isolated_numbers() {
+        // The left column is only made of numbers, the right part opens at once
+        let tile_map: TileMap = ".*...\n.*...\n.*...".parse().unwrap();
+        assert_eq!(tile_map.bbbv(), 4);
+        assert_eq!(tile_map.openings(), 1);
+        assert_eq!(tile_map.solved_bbbv(|c| c == Coordinates { x: 4, y: 0 }), 1);
+        assert_eq!(tile_map.solved_bbbv(|c| c.x == 0), 3);
+        assert_eq!(tile_map.solved_bbbv(|_| true), 4);
+    }
+    #[test]
+    fn no_guess_boards_are_solvable() {
+        let start = Coordinates { x: 4, y: 4 };
+        for seed in 0..5 {
+            let mut tile_map = TileMap::empty(9, 9);
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+            tile_map.set_bombs_no_guess(10, start, 100, &mut rng).unwrap();
+            assert_eq!(tile_map.bomb_count(), 10);
+            assert!(solver::solves_without_guessing(&tile_map, start));
+        }
+    }
+
+    #[test]
+    fn rejects_too_many_bombs() {
+        let mut tile_map = TileMap::empty(3, 3);
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let safe_tiles = tile_map.safe_zone(Coordinates { x: 0, y: 0 }, true);
+        assert_eq!(
+            tile_map.set_bombs_around(6, &safe_tiles, &mut rng),
+            Err(BoardError::TooManyBombs { bomb_count: 6, tile_count: 9 })
+        );
+        assert_eq!(tile_map.bomb_count(), 0);
+        assert!(tile_map.set_bombs_around(5, &safe_tiles, &mut rng).is_ok());
+    }
+}
\ No newline at end of file
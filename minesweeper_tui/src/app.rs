@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+use minesweeper_core::{Change, Coordinates, Game, GameOptions};
+use rand::{thread_rng, Rng};
+
+/// Game played in the terminal with the tile under the cursor
+pub struct App {
+    pub options: GameOptions,
+    pub game: Game,
+    pub cursor: Coordinates,
+    /// The revealed bomb that lost the game
+    pub exploded: Option<Coordinates>,
+    /// Message shown under the board
+    pub status: Option<String>,
+    /// Set on the first uncovered tile
+    started_at: Option<Instant>,
+    /// Game time once the game is over
+    final_time: Option<Duration>,
+}
+
+impl App {
+    pub fn new(options: GameOptions) -> Self {
+        let game = Self::create_game(&options);
+        let mut app = Self {
+            options,
+            game,
+            cursor: Coordinates::default(),
+            exploded: None,
+            status: None,
+            started_at: None,
+            final_time: None,
+        };
+        app.reset();
+        app
+    }
+
+    /// Replaces the game with a new one, with the same options
+    pub fn new_game(&mut self) {
+        self.game = Self::create_game(&self.options);
+        self.reset();
+    }
+
+    /// Moves the cursor, `dy` going up, staying on the board
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let tile_map = self.game.tile_map();
+        if tile_map.width() == 0 || tile_map.height() == 0 {
+            return;
+        }
+        let x = (self.cursor.x as i32 + dx).clamp(0, tile_map.width() as i32 - 1);
+        let y = (self.cursor.y as i32 + dy).clamp(0, tile_map.height() as i32 - 1);
+        self.cursor = Coordinates { x: x as u16, y: y as u16 };
+    }
+
+    /// Uncovers the tile under the cursor, or chords it when it is already uncovered
+    pub fn reveal(&mut self) {
+        let changes = match self.game.is_covered(self.cursor) {
+            true => self.game.reveal(self.cursor),
+            false => self.game.chord(self.cursor),
+        };
+        self.apply(changes);
+    }
+
+    pub fn flag(&mut self) {
+        let changes = self.game.flag(self.cursor);
+        self.apply(changes);
+    }
+
+    pub fn chord(&mut self) {
+        let changes = self.game.chord(self.cursor);
+        self.apply(changes);
+    }
+
+    /// Time spent since the first uncovered tile, stops when the game is over
+    pub fn elapsed(&self) -> Duration {
+        match (self.final_time, self.started_at) {
+            (Some(time), _) => time,
+            (None, Some(started_at)) => started_at.elapsed(),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
+    fn create_game(options: &GameOptions) -> Game {
+        let seed = options.seed.unwrap_or_else(|| thread_rng().gen());
+        // Loaded options are validated, only options built in code can fail
+        Game::new(options.map_size, options.bomb_count, options.generation.clone(), seed)
+            .unwrap_or_else(|_| Self::create_game(&GameOptions::default()))
+    }
+
+    fn reset(&mut self) {
+        let tile_map = self.game.tile_map();
+        self.cursor = Coordinates {
+            x: tile_map.width() / 2,
+            y: tile_map.height() / 2,
+        };
+        self.exploded = None;
+        self.started_at = None;
+        self.final_time = None;
+        if self.options.safe_start {
+            let changes = self.game.safe_start();
+            self.apply(changes);
+        }
+    }
+
+    /// Starts and stops the timer and remembers the exploded bomb
+    fn apply(&mut self, changes: Vec<Change>) {
+        for change in changes {
+            if let Change::Exploded(coords) = change {
+                self.exploded = Some(coords);
+            }
+        }
+        if self.started_at.is_none() && self.game.has_started() {
+            self.started_at = Some(Instant::now());
+        }
+        if self.final_time.is_none() && self.game.is_over() {
+            self.final_time = Some(self.elapsed());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::io;
+use std::time::Duration;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::DefaultTerminal;
+use minesweeper_core::{settings, GameOptions};
+use app::App;
+
+mod app;
+mod ui;
+
+/// Longest wait for input, the timer is redrawn in between
+const TICK: Duration = Duration::from_millis(100);
+
+fn main() -> io::Result<()> {
+    let mut status = None;
+    let options = match settings::load_options() {
+        Ok(options) => options.unwrap_or_default(),
+        Err(e) => {
+            status = Some(format!("{}, using the default options", e));
+            GameOptions::default()
+        }
+    };
+    let mut app = App::new(options);
+    app.status = status;
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, app);
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Left | KeyCode::Char('h') => app.move_cursor(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => app.move_cursor(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => app.move_cursor(0, 1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_cursor(0, -1),
+            KeyCode::Char(' ') | KeyCode::Enter => app.reveal(),
+            KeyCode::Char('f') => app.flag(),
+            KeyCode::Char('c') => app.chord(),
+            KeyCode::Char('n') => app.new_game(),
+            _ => (),
+        }
+    }
+}
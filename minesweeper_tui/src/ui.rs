@@ -0,0 +1,115 @@
+use minesweeper_core::{Coordinates, GameState, Tile};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::Frame;
+use crate::app::App;
+
+const FACE_PLAYING: &str = ":)";
+const FACE_WON: &str = "B)";
+const FACE_LOST: &str = "X(";
+
+/// Terminal columns of a tile, the symbol and a space
+const TILE_WIDTH: u16 = 2;
+
+const HELP: &str = "arrows/hjkl move  space reveal  f flag  c chord  n new  q quit";
+
+/// Draws the bomb counter, face and timer above the board and the controls below it
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header_area, board_area, footer_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .areas(frame.area());
+
+    let face = match app.game.state() {
+        GameState::Playing => FACE_PLAYING,
+        GameState::Won => FACE_WON,
+        GameState::Lost => FACE_LOST,
+    };
+    let header = format!(
+        "{:03}  {}  {:03}",
+        app.game.remaining_bombs(),
+        face,
+        app.elapsed().as_secs()
+    );
+    frame.render_widget(Paragraph::new(header).centered(), header_area);
+
+    let tile_map = app.game.tile_map();
+    // Boards larger than the terminal are cut, the border included
+    let width = tile_map
+        .width()
+        .saturating_mul(TILE_WIDTH)
+        .saturating_add(2)
+        .min(board_area.width);
+    let height = tile_map.height().saturating_add(2).min(board_area.height);
+    let area = Rect {
+        x: board_area.x + (board_area.width - width) / 2,
+        y: board_area.y + (board_area.height - height) / 2,
+        width,
+        height,
+    };
+    // The top row is the last one of the map
+    let rows: Vec<Line> = (0..tile_map.height())
+        .rev()
+        .map(|y| {
+            (0..tile_map.width())
+                .map(|x| tile_span(app, Coordinates { x, y }))
+                .collect::<Vec<_>>()
+                .into()
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(rows).block(Block::bordered().title(" minesweeper ")),
+        area,
+    );
+
+    let mut footer = vec![Line::from(HELP)];
+    if let Some(status) = &app.status {
+        footer.push(Line::styled(status.as_str(), Style::new().fg(Color::Red)));
+    }
+    frame.render_widget(Paragraph::new(footer).centered(), footer_area);
+}
+
+fn tile_span(app: &App, coordinates: Coordinates) -> Span<'static> {
+    let game = &app.game;
+    let tile = game.tile_map()[coordinates.y as usize][coordinates.x as usize];
+    let (symbol, mut style) = if game.is_covered(coordinates) {
+        match game.is_flagged(coordinates) {
+            // Wrong flags are shown once the game is lost
+            true if game.state() == GameState::Lost && !tile.is_bomb() => {
+                ("X", Style::new().fg(Color::Magenta))
+            }
+            true => ("F", Style::new().fg(Color::Red)),
+            false => ("#", Style::new().fg(Color::DarkGray)),
+        }
+    } else {
+        match tile {
+            Tile::Bomb if app.exploded == Some(coordinates) => {
+                ("*", Style::new().fg(Color::White).bg(Color::Red))
+            }
+            Tile::Bomb => ("*", Style::new().fg(Color::LightRed)),
+            Tile::BombNeighbor(v) => (NUMBERS[v as usize], number_style(v)),
+            Tile::Empty => (" ", Style::new()),
+        }
+    };
+    if coordinates == app.cursor {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    Span::styled(format!("{:<width$}", symbol, width = TILE_WIDTH as usize), style)
+}
+
+const NUMBERS: [&str; 9] = ["0", "1", "2", "3", "4", "5", "6", "7", "8"];
+
+/// Same colors as the console output of the maps
+fn number_style(count: u8) -> Style {
+    let color = match count {
+        1 => Color::Cyan,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        _ => Color::Red,
+    };
+    Style::new().fg(color)
+}
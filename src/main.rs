@@ -1,15 +1,49 @@
 use bevy::prelude::*;
+use bevy::asset::{AssetServerSettings, LoadState};
 use bevy::log;
+use board_plugin::resources::Board;
 use board_plugin::resources::BoardAssets;
-use board_plugin::resources::SpriteMaterial;
-use board_plugin::{BoardPlugin, resources::BoardOptions};
+use board_plugin::resources::BoardTheme;
+use board_plugin::resources::ReplayPlayer;
+use board_plugin::{BoardPlugin, hud::HudPlugin, resources::{BoardOptions, Difficulty}};
+use board_plugin::events::{LoadGameEvent, PlayReplayEvent, SaveGameEvent, SaveReplayEvent};
+use std::time::Duration;
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::WorldInspectorPlugin;
 
+mod menu;
+mod settings;
+mod stats;
+
+/// Themes cycled through with the T key, the first one is loaded at startup
+const THEMES: [&str; 2] = ["themes/default.theme.ron", "themes/dark.theme.ron"];
+
+/// Game saved with F5 and loaded with F9, next to the working directory
+const SAVE_PATH: &str = "savegame.ron";
+
+/// Replay saved with F6 and played with F7, next to the working directory
+const REPLAY_PATH: &str = "replay.ron";
+
+/// Board exported with F8 in the Minesweeper Arbiter format, next to the working directory
+const BOARD_PATH: &str = "board.mbf";
+
+/// Replay seek step of the left and right keys
+const SEEK_STEP: Duration = Duration::from_secs(5);
+
+/// Index in `THEMES` of the selected theme
+#[derive(Debug, Default)]
+pub struct SelectedTheme(usize);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum AppState {
+    MainMenu,
+    Settings,
+    Statistics,
     InGame,
+    /// Pushed over `InGame` once the game is won or lost
+    GameOver,
+    /// Pushed over `InGame` to pause it, also the state while the theme loads
     Out,
 }
 
@@ -22,14 +56,35 @@ fn main() {
         height: 800.0,
         ..Default::default()
     })
+    // Reloads sprites, fonts and themes edited while the game is running
+    .insert_resource(AssetServerSettings {
+        watch_for_changes: true,
+        ..Default::default()
+    })
     .add_state(AppState::Out)
     .add_plugins(DefaultPlugins)
     .add_plugin(BoardPlugin {
         running_state: AppState::InGame,
     })
-    .add_system(state_handler);
+    .add_plugin(HudPlugin {
+        running_state: AppState::InGame,
+    })
+    .add_plugin(menu::MenuPlugin)
+    .add_system_set(
+        SystemSet::on_in_stack_update(AppState::InGame)
+            .with_system(stats::record_game)
+    )
+    .init_resource::<SelectedTheme>()
+    .add_system(state_handler)
+    .add_system(difficulty_handler)
+    .add_system(theme_handler)
+    .add_system(save_handler)
+    .add_system(replay_handler)
+    .add_system(start_game)
+    .add_system(settings::save_changed_options);
 
     app.add_startup_system(setup_board);
+    app.add_startup_system(stats::setup_statistics);
 
     #[cfg(feature = "debug")]
     app.add_plugin(WorldInspectorPlugin::new());
@@ -40,73 +95,228 @@ fn main() {
 
 fn setup_board(
     mut commands: Commands,
-    mut state: ResMut<State<AppState>>,
     asset_server: Res<AssetServer>,
 ) {
-    commands.insert_resource(BoardOptions {
-        map_size: (20,20),
-        bomb_count: 40,
-        tile_padding: 1.0,
-        safe_start: true,
-        ..Default::default()
-    });
+    let board_options = match settings::load_options() {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            log::info!("No {} found, using default settings", settings::SETTINGS_PATH);
+            default_options()
+        }
+        Err(e) => {
+            log::error!("{}, using default settings", e);
+            default_options()
+        }
+    };
+    commands.insert_resource(board_options);
 
-    commands.insert_resource(BoardAssets {
-        label: "Default".to_string(),
-        board_material: SpriteMaterial {
-            color: Color::WHITE,
-            ..Default::default()
-        },
-        tile_material: SpriteMaterial {
-            color: Color::DARK_GRAY,
-            ..Default::default()
-        },
-        covered_tile_material: SpriteMaterial {
-            color: Color::GRAY,
-            ..Default::default()
-        },
-        bomb_counter_font: asset_server.load("fonts/pixeled.ttf"),
-        bomb_counter_colors: BoardAssets::default_colors(),
-        flag_material: SpriteMaterial {
-            texture: asset_server.load("sprites/flag.png"),
-            color: Color::WHITE,
-        },
-        bomb_material: SpriteMaterial {
-            texture: asset_server.load("sprites/bomb.png"),
-            color: Color::WHITE,
-        },
+    commands.insert_resource(BoardTheme {
+        handle: asset_server.load(THEMES[0]),
     });
+}
+
+/// Shows the main menu once the board theme is loaded,
+/// with the built-in theme if it can't be
+fn start_game(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    board_assets: Option<Res<BoardAssets>>,
+    board_theme: Res<BoardTheme>,
+    selected_theme: Res<SelectedTheme>,
+    asset_server: Res<AssetServer>,
+    mut started: Local<bool>,
+) {
+    if *started {
+        return;
+    }
+    if board_assets.is_none() {
+        if asset_server.get_load_state(&board_theme.handle) != LoadState::Failed {
+            return;
+        }
+        log::error!("Failed to load the {} theme, using the built-in one", THEMES[selected_theme.0]);
+        commands.insert_resource(BoardAssets::builtin(&asset_server));
+    }
+    *started = true;
+    if let Err(e) = state.set(AppState::MainMenu) {
+        log::error!("Failed to show the main menu: {}", e);
+    }
+}
 
-    state.set(AppState::InGame).unwrap();
+fn theme_handler(
+    mut selected_theme: ResMut<SelectedTheme>,
+    mut board_theme: ResMut<BoardTheme>,
+    asset_server: Res<AssetServer>,
+    keys: Res<Input<KeyCode>>,
+) {
+    if keys.just_pressed(KeyCode::T) {
+        next_theme(&mut selected_theme, &mut board_theme, &asset_server);
+    }
+}
+
+/// Switches to the theme following the selected one in `THEMES`
+fn next_theme(selected_theme: &mut SelectedTheme, board_theme: &mut BoardTheme, asset_server: &AssetServer) {
+    selected_theme.0 = (selected_theme.0 + 1) % THEMES.len();
+    log::info!("switching to {} theme", THEMES[selected_theme.0]);
+    board_theme.handle = asset_server.load(THEMES[selected_theme.0]);
+}
+
+fn save_handler(
+    mut state: ResMut<State<AppState>>,
+    mut save_game_ewr: EventWriter<SaveGameEvent>,
+    mut load_game_ewr: EventWriter<LoadGameEvent>,
+    board: Option<Res<Board>>,
+    keys: Res<Input<KeyCode>>,
+) {
+    if keys.just_pressed(KeyCode::F5) {
+        log::debug!("saving detected");
+        save_game_ewr.send(SaveGameEvent(SAVE_PATH.into()));
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        log::debug!("loading save detected");
+        // The saved game replaces a running one
+        if state.current() != &AppState::InGame {
+            load_game(&mut state);
+        }
+        load_game_ewr.send(LoadGameEvent(SAVE_PATH.into()));
+    }
+    if keys.just_pressed(KeyCode::F8) {
+        log::debug!("board export detected");
+        match board {
+            Some(board) if board.game.generated() => match board.game.tile_map().save_mbf(BOARD_PATH) {
+                Ok(()) => log::info!("Exported board to {}", BOARD_PATH),
+                Err(e) => log::error!("Failed to export board: {}", e),
+            },
+            _ => log::warn!("The bombs are not placed yet, nothing to export"),
+        }
+    }
+}
+
+fn replay_handler(
+    mut state: ResMut<State<AppState>>,
+    mut save_replay_ewr: EventWriter<SaveReplayEvent>,
+    mut play_replay_ewr: EventWriter<PlayReplayEvent>,
+    replay_player: Option<ResMut<ReplayPlayer>>,
+    keys: Res<Input<KeyCode>>,
+) {
+    if keys.just_pressed(KeyCode::F6) {
+        log::debug!("replay saving detected");
+        save_replay_ewr.send(SaveReplayEvent(REPLAY_PATH.into()));
+    }
+    if keys.just_pressed(KeyCode::F7) {
+        log::debug!("replay playing detected");
+        if state.current() != &AppState::InGame {
+            load_game(&mut state);
+        }
+        play_replay_ewr.send(PlayReplayEvent(REPLAY_PATH.into()));
+    }
+
+    // Playback controls
+    let mut player = match replay_player {
+        Some(player) => player,
+        None => return,
+    };
+    if keys.just_pressed(KeyCode::Space) {
+        player.toggle_pause();
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        let speed = player.speed * 2.0;
+        player.set_speed(speed);
+        log::info!("replay speed x{}", player.speed);
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        let speed = player.speed / 2.0;
+        player.set_speed(speed);
+        log::info!("replay speed x{}", player.speed);
+    }
+    if keys.just_pressed(KeyCode::Right) {
+        let time = player.clock + SEEK_STEP;
+        player.seek(time);
+    }
+    if keys.just_pressed(KeyCode::Left) {
+        let time = player.clock.saturating_sub(SEEK_STEP);
+        player.seek(time);
+    }
+}
+
+fn default_options() -> BoardOptions {
+    BoardOptions {
+        tile_padding: 1.0,
+        ..Default::default()
+    }
 }
 
 fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>) {
     if keys.just_pressed(KeyCode::C) {
         log::debug!("clearing detected");
-        if state.current() == &AppState::InGame {
+        if matches!(state.current(), AppState::InGame | AppState::GameOver) {
             log::info!("clearing game");
-            state.set(AppState::Out).unwrap();
+            if let Err(e) = state.replace(AppState::MainMenu) {
+                log::error!("Failed to clear the game: {}", e);
+            }
         }
     }
     if keys.just_pressed(KeyCode::G) {
         log::debug!("loading detected");
-        if state.current() == &AppState::Out {
-            log::info!("loading game");
-            state.set(AppState::InGame).unwrap();
-        } else if state.current() == &AppState::InGame {
-            log::info!("loading game");
-            state.restart().unwrap();
-        }
+        load_game(&mut state);
     }
+    // Pauses or resumes the game, the board is hidden meanwhile
     if keys.just_pressed(KeyCode::Escape) {
-        if state.current() == &AppState::InGame {
-            state.overwrite_push(AppState::Out).unwrap();
-        } else if !state.inactives().is_empty() {
-            state.pop().unwrap();
+        let result = match state.current() {
+            AppState::InGame => state.overwrite_push(AppState::Out),
+            AppState::Out if !state.inactives().is_empty() => state.pop(),
+            AppState::Settings | AppState::Statistics => state.set(AppState::MainMenu),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to handle escape: {}", e);
+        }
+    }
+}
+
+fn difficulty_handler(
+    mut state: ResMut<State<AppState>>,
+    mut board_options: ResMut<BoardOptions>,
+    keys: Res<Input<KeyCode>>,
+) {
+    let difficulty = if keys.just_pressed(KeyCode::Key1) {
+        Difficulty::Beginner
+    } else if keys.just_pressed(KeyCode::Key2) {
+        Difficulty::Intermediate
+    } else if keys.just_pressed(KeyCode::Key3) {
+        Difficulty::Expert
+    } else {
+        return;
+    };
+    log::info!("switching to {:?} difficulty", difficulty);
+    board_options.game.set_difficulty(difficulty);
+    // Menus only change the setting for the next game
+    if matches!(state.current(), AppState::InGame | AppState::Out | AppState::GameOver) {
+        load_game(&mut state);
+    }
+}
+
+/// Starts a new game, restarting the current one if needed
+fn load_game(state: &mut State<AppState>) {
+    match state.current() {
+        AppState::InGame => {
+            log::info!("loading game");
+            if let Err(e) = state.restart() {
+                log::error!("Failed to restart the game: {}", e);
+            }
+        }
+        // The theme is still loading
+        AppState::Out if state.inactives().is_empty() => (),
+        _ => {
+            log::info!("loading game");
+            // Unwinds a paused or finished game before starting the new one
+            if let Err(e) = state.replace(AppState::InGame) {
+                log::error!("Failed to load the game: {}", e);
+            }
         }
     }
 }
 
 fn camera_setup(mut commands: Commands) {
     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
 }
\ No newline at end of file
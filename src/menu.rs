@@ -0,0 +1,371 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::log;
+use board_plugin::resources::{Board, BoardAssets, BoardOptions, BoardTheme, Difficulty, GameStats};
+use minesweeper_core::GameState;
+use crate::{AppState, SelectedTheme};
+use crate::stats::{DifficultyStats, Statistics};
+
+/// Root node of a menu screen, despawned when leaving its state
+#[derive(Debug, Copy, Clone, Component)]
+pub struct MenuRoot;
+
+/// Text showing the selected difficulty on the settings screen
+#[derive(Debug, Copy, Clone, Component)]
+pub struct DifficultyText;
+
+/// Text showing the selected theme on the settings screen
+#[derive(Debug, Copy, Clone, Component)]
+pub struct ThemeText;
+
+/// Action of a menu button
+#[derive(Debug, Copy, Clone, PartialEq, Component)]
+pub enum MenuButton {
+    Play,
+    Settings,
+    Statistics,
+    Quit,
+    Difficulty(Difficulty),
+    NextTheme,
+    /// Back to the main menu
+    MainMenu,
+}
+
+const TITLE_FONT_SIZE: f32 = 50.0;
+const FONT_SIZE: f32 = 25.0;
+const STATS_FONT_SIZE: f32 = 15.0;
+const BUTTON_WIDTH: f32 = 300.0;
+const BUTTON_HEIGHT: f32 = 50.0;
+const BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.35, 0.35, 0.35);
+const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.2, 0.6, 0.2);
+
+/// Main menu, settings and game over screens
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(AppState::MainMenu)
+                .with_system(spawn_main_menu)
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::MainMenu)
+                .with_system(button_handler)
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::MainMenu)
+                .with_system(despawn_menu)
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::Settings)
+                .with_system(spawn_settings_menu)
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Settings)
+                .with_system(button_handler)
+                .with_system(update_settings_texts)
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::Settings)
+                .with_system(despawn_menu)
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::Statistics)
+                .with_system(spawn_statistics_menu)
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::Statistics)
+                .with_system(button_handler)
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::Statistics)
+                .with_system(despawn_menu)
+        )
+        // Pushed on top of the game, which keeps the revealed board on screen
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame)
+                .with_system(game_over_handler)
+        )
+        .add_system_set(
+            SystemSet::on_enter(AppState::GameOver)
+                .with_system(spawn_game_over_menu)
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::GameOver)
+                .with_system(button_handler)
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::GameOver)
+                .with_system(despawn_menu)
+        );
+        log::info!("Loaded Menu Plugin");
+    }
+}
+
+fn text_bundle(value: &str, board_assets: &BoardAssets, font_size: f32) -> TextBundle {
+    TextBundle {
+        text: Text::with_section(
+            value,
+            TextStyle {
+                font: board_assets.bomb_counter_font.clone(),
+                font_size,
+                color: Color::WHITE,
+            },
+            Default::default(),
+        ),
+        style: Style {
+            margin: Rect::all(Val::Px(10.0)),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, label: &str, action: MenuButton, board_assets: &BoardAssets) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(BUTTON_WIDTH), Val::Px(BUTTON_HEIGHT)),
+                margin: Rect::all(Val::Px(5.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: BUTTON_COLOR.into(),
+            ..Default::default()
+        })
+        .insert(Name::new(format!("{} Button", label)))
+        .insert(action)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    label,
+                    TextStyle {
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Spawns a centered column, listed from the top like the HUD
+fn spawn_menu(commands: &mut Commands, name: &str, color: Color, children: impl FnOnce(&mut ChildBuilder)) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: color.into(),
+            ..Default::default()
+        })
+        .insert(Name::new(name.to_string()))
+        .insert(MenuRoot)
+        .with_children(children);
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Custom { width, height, mines } => {
+            format!("Custom {}x{}, {} mines", width, height, mines)
+        }
+        difficulty => format!("{:?}", difficulty),
+    }
+}
+
+fn spawn_main_menu(mut commands: Commands, board_assets: Res<BoardAssets>) {
+    spawn_menu(&mut commands, "Main Menu", Color::BLACK, |parent| {
+        parent.spawn_bundle(text_bundle("Minesweeper", &board_assets, TITLE_FONT_SIZE));
+        spawn_button(parent, "Play", MenuButton::Play, &board_assets);
+        spawn_button(parent, "Settings", MenuButton::Settings, &board_assets);
+        spawn_button(parent, "Statistics", MenuButton::Statistics, &board_assets);
+        spawn_button(parent, "Quit", MenuButton::Quit, &board_assets);
+    });
+}
+
+fn spawn_settings_menu(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    board_options: Res<BoardOptions>,
+) {
+    spawn_menu(&mut commands, "Settings Menu", Color::BLACK, |parent| {
+        parent.spawn_bundle(text_bundle("Settings", &board_assets, TITLE_FONT_SIZE));
+        parent
+            .spawn_bundle(text_bundle(&difficulty_label(board_options.game.difficulty()), &board_assets, FONT_SIZE))
+            .insert(DifficultyText);
+        for difficulty in Difficulty::PRESETS {
+            spawn_button(parent, &difficulty_label(difficulty), MenuButton::Difficulty(difficulty), &board_assets);
+        }
+        parent
+            .spawn_bundle(text_bundle(&board_assets.label, &board_assets, FONT_SIZE))
+            .insert(ThemeText);
+        spawn_button(parent, "Next theme", MenuButton::NextTheme, &board_assets);
+        spawn_button(parent, "Back", MenuButton::MainMenu, &board_assets);
+    });
+}
+
+fn stats_label(stats: &DifficultyStats) -> String {
+    let time = |time: Option<f32>| match time {
+        Some(time) => format!("{:.1}s", time),
+        None => "-".to_string(),
+    };
+    format!(
+        "{}: {}/{} won, streak {} (best {}), best {}, average {}",
+        difficulty_label(stats.difficulty),
+        stats.won,
+        stats.played,
+        stats.current_streak,
+        stats.best_streak,
+        time(stats.best_time),
+        time(stats.average_time()),
+    )
+}
+
+fn spawn_statistics_menu(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    statistics: Res<Statistics>,
+) {
+    spawn_menu(&mut commands, "Statistics Menu", Color::BLACK, |parent| {
+        parent.spawn_bundle(text_bundle("Statistics", &board_assets, TITLE_FONT_SIZE));
+        // Presets first, even when never played
+        let presets = Difficulty::PRESETS
+            .into_iter()
+            .map(|difficulty| statistics.get(difficulty).cloned().unwrap_or_else(|| DifficultyStats::new(difficulty)));
+        let customs = statistics
+            .difficulties
+            .iter()
+            .filter(|stats| matches!(stats.difficulty, Difficulty::Custom { .. }))
+            .cloned();
+        for stats in presets.chain(customs) {
+            parent.spawn_bundle(text_bundle(&stats_label(&stats), &board_assets, STATS_FONT_SIZE));
+        }
+        spawn_button(parent, "Back", MenuButton::MainMenu, &board_assets);
+    });
+}
+
+/// Shows the outcome and statistics of the finished game over the revealed board
+fn spawn_game_over_menu(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    board: Res<Board>,
+    game_stats: Res<GameStats>,
+) {
+    let title = match board.game.state() == GameState::Won {
+        true => "You win!",
+        false => "Game over",
+    };
+    let color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+    spawn_menu(&mut commands, "Game Over Menu", color, |parent| {
+        parent.spawn_bundle(text_bundle(title, &board_assets, TITLE_FONT_SIZE));
+        let difficulty = difficulty_label(board.options.game.difficulty());
+        parent.spawn_bundle(text_bundle(&difficulty, &board_assets, FONT_SIZE));
+        let time = format!("Time: {:.1}s", game_stats.elapsed.as_secs_f32());
+        parent.spawn_bundle(text_bundle(&time, &board_assets, FONT_SIZE));
+        let hints = format!("Hints: {}", game_stats.hints);
+        parent.spawn_bundle(text_bundle(&hints, &board_assets, FONT_SIZE));
+        let summary = game_stats.summary(board.game.tile_map().bbbv(), board.game.solved_bbbv());
+        let lines = [
+            format!("3BV: {}/{}", summary.solved_bbbv, summary.bbbv),
+            format!("Openings: {}", board.game.tile_map().openings()),
+            format!("3BV/s: {:.2}", summary.bbbv_per_second),
+            format!("IOE: {:.2}", summary.ioe),
+            format!("Clicks: {} ({:.2}/s)", game_stats.clicks(), summary.clicks_per_second),
+        ];
+        for line in lines {
+            parent.spawn_bundle(text_bundle(&line, &board_assets, STATS_FONT_SIZE));
+        }
+        spawn_button(parent, "Play again", MenuButton::Play, &board_assets);
+        spawn_button(parent, "Main menu", MenuButton::MainMenu, &board_assets);
+    });
+}
+
+fn despawn_menu(mut commands: Commands, menus: Query<Entity, With<MenuRoot>>) {
+    for entity in menus.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn button_handler(
+    mut state: ResMut<State<AppState>>,
+    mut board_options: ResMut<BoardOptions>,
+    mut selected_theme: ResMut<SelectedTheme>,
+    mut board_theme: ResMut<BoardTheme>,
+    asset_server: Res<AssetServer>,
+    mut app_exit_ewr: EventWriter<AppExit>,
+    mut buttons: Query<(&Interaction, &MenuButton, &mut UiColor), Changed<Interaction>>,
+) {
+    for (interaction, button, mut color) in buttons.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => PRESSED_BUTTON_COLOR,
+            Interaction::Hovered => HOVERED_BUTTON_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        }
+        .into();
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        log::debug!("{:?} clicked", button);
+        let result = match button {
+            // Replacing unwinds the finished game below the game over screen
+            MenuButton::Play => state.replace(AppState::InGame),
+            MenuButton::Settings => state.set(AppState::Settings),
+            MenuButton::Statistics => state.set(AppState::Statistics),
+            MenuButton::MainMenu => state.replace(AppState::MainMenu),
+            MenuButton::Quit => {
+                app_exit_ewr.send(AppExit);
+                Ok(())
+            }
+            MenuButton::Difficulty(difficulty) => {
+                board_options.game.set_difficulty(*difficulty);
+                Ok(())
+            }
+            MenuButton::NextTheme => {
+                crate::next_theme(&mut selected_theme, &mut board_theme, &asset_server);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            log::error!("Failed to handle {:?}: {}", button, e);
+        }
+    }
+}
+
+fn update_settings_texts(
+    board_options: Res<BoardOptions>,
+    board_assets: Res<BoardAssets>,
+    mut difficulty_texts: Query<&mut Text, (With<DifficultyText>, Without<ThemeText>)>,
+    mut theme_texts: Query<&mut Text, (With<ThemeText>, Without<DifficultyText>)>,
+) {
+    if board_options.is_changed() {
+        for mut text in difficulty_texts.iter_mut() {
+            text.sections[0].value = difficulty_label(board_options.game.difficulty());
+        }
+    }
+    if board_assets.is_changed() {
+        for mut text in theme_texts.iter_mut() {
+            text.sections[0].value = board_assets.label.clone();
+        }
+    }
+}
+
+/// Shows the game over screen once the game is won or lost
+fn game_over_handler(mut state: ResMut<State<AppState>>, board: Res<Board>) {
+    if !board.game.is_over() {
+        return;
+    }
+    if let Err(e) = state.push(AppState::GameOver) {
+        log::error!("Failed to show the game over screen: {}", e);
+    }
+
+}
\ No newline at end of file
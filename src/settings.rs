@@ -0,0 +1,27 @@
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::resources::BoardOptions;
+use minesweeper_core::errors::SettingsError;
+use minesweeper_core::settings;
+
+pub use minesweeper_core::settings::SETTINGS_PATH;
+
+/// Reads the board options, `None` if no settings were saved yet
+pub fn load_options() -> Result<Option<BoardOptions>, SettingsError> {
+    let options: Option<BoardOptions> = settings::load_settings()?;
+    if let Some(options) = &options {
+        options.game.validate().map_err(SettingsError::Invalid)?;
+    }
+    Ok(options)
+}
+
+/// Writes the board options back whenever they are changed in game
+pub fn save_changed_options(board_options: Res<BoardOptions>) {
+    if !board_options.is_changed() || board_options.is_added() {
+        return;
+    }
+    match settings::save_settings(&*board_options) {
+        Ok(()) => log::info!("Saved settings to {}", SETTINGS_PATH),
+        Err(e) => log::error!("Failed to save settings: {}", e),
+    }
+}
\ No newline at end of file
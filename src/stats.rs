@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt::{ self, Display, Formatter };
+use std::path::PathBuf;
+use std::{env, fs, io};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::{BoardCompletedEvent, BombExplosionEvent};
+use board_plugin::resources::{Board, Difficulty, GameStats, ReplayPlayer};
+use ron::ser::PrettyConfig;
+use serde::{ Deserialize, Serialize };
+
+/// Folder of the game inside the user data dir
+const DATA_FOLDER: &str = "minesweeper-tutorial";
+const STATS_FILE: &str = "stats.ron";
+
+#[derive(Debug)]
+pub enum StatsError {
+    Io(io::Error),
+    Parse(ron::Error),
+}
+
+/// Results of the finished games of one difficulty
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyStats {
+    pub difficulty: Difficulty,
+    pub played: u32,
+    pub won: u32,
+    /// Games won in a row, reset by a loss
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Fastest win in seconds
+    pub best_time: Option<f32>,
+    /// Sum of the win times in seconds
+    pub total_time: f32,
+}
+
+/// Statistics of every difficulty played, stored in the user data dir. Must be used as a resource.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Statistics {
+    pub difficulties: Vec<DifficultyStats>,
+}
+
+impl Display for StatsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "could not access {}: {}", stats_path().display(), e),
+            Self::Parse(e) => write!(f, "malformed {}: {}", stats_path().display(), e),
+        }
+    }
+}
+
+impl Error for StatsError {}
+
+impl DifficultyStats {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            played: 0,
+            won: 0,
+            current_streak: 0,
+            best_streak: 0,
+            best_time: None,
+            total_time: 0.0,
+        }
+    }
+
+    /// Average win time in seconds
+    pub fn average_time(&self) -> Option<f32> {
+        match self.won {
+            0 => None,
+            won => Some(self.total_time / won as f32),
+        }
+    }
+
+    pub fn record_win(&mut self, time: f32) {
+        self.played += 1;
+        self.won += 1;
+        self.current_streak += 1;
+        self.best_streak = self.best_streak.max(self.current_streak);
+        self.total_time += time;
+        self.best_time = Some(self.best_time.map_or(time, |best| best.min(time)));
+    }
+
+    pub fn record_loss(&mut self) {
+        self.played += 1;
+        self.current_streak = 0;
+    }
+}
+
+impl Statistics {
+    pub fn get(&self, difficulty: Difficulty) -> Option<&DifficultyStats> {
+        self.difficulties.iter().find(|s| s.difficulty == difficulty)
+    }
+
+    pub fn get_mut(&mut self, difficulty: Difficulty) -> &mut DifficultyStats {
+        match self.difficulties.iter().position(|s| s.difficulty == difficulty) {
+            Some(i) => &mut self.difficulties[i],
+            None => {
+                self.difficulties.push(DifficultyStats::new(difficulty));
+                self.difficulties.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Reads the statistics, empty ones if none were saved yet
+    pub fn load() -> Result<Self, StatsError> {
+        let content = match fs::read_to_string(stats_path()) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(StatsError::Io(e)),
+        };
+        ron::from_str(&content).map_err(StatsError::Parse)
+    }
+
+    pub fn save(&self) -> Result<(), StatsError> {
+        let path = stats_path();
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder).map_err(StatsError::Io)?;
+        }
+        let content = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(StatsError::Parse)?;
+        fs::write(path, content).map_err(StatsError::Io)
+    }
+}
+
+/// Platform data dir of the user, the working directory if it can't be found
+fn data_dir() -> PathBuf {
+    let var = |name| env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    let dir = if cfg!(windows) {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local/share")))
+    };
+    dir.unwrap_or_default()
+}
+
+pub fn stats_path() -> PathBuf {
+    data_dir().join(DATA_FOLDER).join(STATS_FILE)
+}
+
+pub fn setup_statistics(mut commands: Commands) {
+    let statistics = match Statistics::load() {
+        Ok(statistics) => statistics,
+        Err(e) => {
+            log::error!("{}, starting with empty statistics", e);
+            Statistics::default()
+        }
+    };
+    commands.insert_resource(statistics);
+}
+
+/// Records the outcome of every finished game
+pub fn record_game(
+    board: Res<Board>,
+    game_stats: Res<GameStats>,
+    mut statistics: ResMut<Statistics>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    replay_player: Option<Res<ReplayPlayer>>,
+) {
+    let lost = bomb_explosion_evr.iter().count() > 0;
+    let won = board_completed_evr.iter().count() > 0;
+    // Replays play recorded games again, they were counted when played
+    if replay_player.is_some() {
+        return;
+    }
+    let stats = if lost {
+        let stats = statistics.get_mut(board.options.game.difficulty());
+        stats.record_loss();
+        stats
+    } else if won {
+        let stats = statistics.get_mut(board.options.game.difficulty());
+        stats.record_win(game_stats.elapsed.as_secs_f32());
+        stats
+    } else {
+        return;
+    };
+    log::info!("{:?}: {} won out of {}", stats.difficulty, stats.won, stats.played);
+    if let Err(e) = statistics.save() {
+        log::error!("Failed to save statistics: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wins_keep_the_best_time() {
+        let mut stats = DifficultyStats::new(Difficulty::Beginner);
+        assert_eq!(stats.average_time(), None);
+        stats.record_win(20.0);
+        stats.record_win(10.0);
+        stats.record_win(30.0);
+        assert_eq!(stats.played, 3);
+        assert_eq!(stats.won, 3);
+        assert_eq!(stats.best_time, Some(10.0));
+        assert_eq!(stats.average_time(), Some(20.0));
+    }
+
+    #[test]
+    fn losses_count_as_played_only() {
+        let mut stats = DifficultyStats::new(Difficulty::Expert);
+        stats.record_loss();
+        stats.record_win(50.0);
+        stats.record_loss();
+        assert_eq!(stats.played, 3);
+        assert_eq!(stats.won, 1);
+        assert_eq!(stats.best_time, Some(50.0));
+        assert_eq!(stats.average_time(), Some(50.0));
+    }
+
+    #[test]
+    fn losses_reset_the_streak() {
+        let mut stats = DifficultyStats::new(Difficulty::Intermediate);
+        stats.record_win(1.0);
+        stats.record_win(1.0);
+        stats.record_win(1.0);
+        stats.record_loss();
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.best_streak, 3);
+        stats.record_win(1.0);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 3);
+    }
+
+    #[test]
+    fn difficulties_are_recorded_apart() {
+        let custom = Difficulty::Custom { width: 5, height: 5, mines: 3 };
+        let mut statistics = Statistics::default();
+        statistics.get_mut(Difficulty::Beginner).record_win(10.0);
+        statistics.get_mut(custom).record_loss();
+        statistics.get_mut(Difficulty::Beginner).record_loss();
+        assert_eq!(statistics.difficulties.len(), 2);
+        assert_eq!(statistics.get(Difficulty::Beginner).map(|s| s.played), Some(2));
+        assert_eq!(statistics.get(custom).map(|s| s.won), Some(0));
+        assert!(statistics.get(Difficulty::Expert).is_none());
+    }
+
+    #[test]
+    fn statistics_are_stored_as_ron() {
+        let mut statistics = Statistics::default();
+        statistics.get_mut(Difficulty::Custom { width: 5, height: 5, mines: 3 }).record_win(12.5);
+        let content = ron::ser::to_string_pretty(&statistics, PrettyConfig::default()).unwrap();
+        let loaded: Statistics = ron::from_str(&content).unwrap();
+        let stats = &loaded.difficulties[0];
+        assert_eq!(stats.difficulty, Difficulty::Custom { width: 5, height: 5, mines: 3 });
+        assert_eq!(stats.best_time, Some(12.5));
+    }
+}
\ No newline at end of file